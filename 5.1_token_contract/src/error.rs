@@ -0,0 +1,173 @@
+// `DecodeError` 与 `PrintProgramError` 在 solana-program 2.x 中已标记弃用，
+// 但 `ProgramError::print` 仍依赖它们输出可读的自定义错误
+#![allow(deprecated)]
+
+use num_derive::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+/// 代币程序自定义错误
+///
+/// 以 `ProgramError::Custom(code)` 的形式返回，`code` 即枚举的序号，
+/// 客户端可通过 `num_traits::FromPrimitive::from_u32` 还原为 `TokenError`
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq, FromPrimitive)]
+pub enum TokenError {
+    // 0: 铸造后的发行量将超过最大发行量
+    #[error("Mint would exceed max supply")]
+    MaxSupplyExceeded,
+    // 1: 代币程序账户不正确
+    #[error("Wrong token program")]
+    WrongTokenProgram,
+    // 2: 系统程序账户不正确
+    #[error("Wrong system program")]
+    WrongSystemProgram,
+    // 3: 缺少必需的签名
+    #[error("Missing required signer")]
+    MissingSigner,
+    // 4: 铸币账户未初始化
+    #[error("Mint is not initialized")]
+    UninitializedMint,
+    // 5: 代币配置 PDA 不正确
+    #[error("Invalid config account")]
+    InvalidConfigAccount,
+    // 6: 关联令牌账户程序不正确
+    #[error("Wrong associated token program")]
+    WrongAssociatedTokenProgram,
+    // 7: 租金系统变量账户不正确
+    #[error("Wrong rent sysvar")]
+    WrongRentSysvar,
+    // 8: 账户必须可写
+    #[error("Account is not writable")]
+    AccountNotWritable,
+    // 9: 关联令牌账户地址与 (owner, mint) 推导结果不一致
+    #[error("Invalid associated token account")]
+    InvalidAssociatedTokenAccount,
+    // 10: 批量铸造的数量个数与接收者账户对数量不一致
+    #[error("Batch amounts do not match recipient account pairs")]
+    BatchLengthMismatch,
+    // 11: 扩展仅支持 Token-2022 程序
+    #[error("Extension requires the Token-2022 program")]
+    ExtensionRequiresToken2022,
+    // 12: 元数据字段超过最大长度
+    #[error("Metadata field too long")]
+    MetadataTooLong,
+    // 13: 元数据 PDA 不正确
+    #[error("Invalid metadata account")]
+    InvalidMetadataAccount,
+    // 14: 签名者不是铸币权限
+    #[error("Signer is not the mint authority")]
+    InvalidMintAuthority,
+    // 15: 归属计划时间或数量参数无效
+    #[error("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    // 16: 归属计划 PDA 不正确
+    #[error("Invalid vesting account")]
+    InvalidVestingAccount,
+    // 17: 当前没有可领取的代币
+    #[error("Nothing to claim")]
+    NothingToClaim,
+    // 18: 空投分发器或领取位图 PDA 不正确
+    #[error("Invalid distributor account")]
+    InvalidDistributorAccount,
+    // 19: 空投叶子数量为 0 或超过位图账户上限
+    #[error("Invalid distributor node count")]
+    InvalidNodeCount,
+    // 20: 默克尔证明校验失败
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof,
+    // 21: 该下标已领取过
+    #[error("Already claimed")]
+    AlreadyClaimed,
+    // 22: 领取下标超出叶子数量
+    #[error("Claim index out of range")]
+    ClaimIndexOutOfRange,
+    // 23: 水龙头配置或领取记录 PDA 不正确
+    #[error("Invalid faucet account")]
+    InvalidFaucetAccount,
+    // 24: 水龙头冷却时间未到
+    #[error("Faucet cooldown active")]
+    FaucetCooldownActive,
+    // 25: 交换报价 PDA 不正确
+    #[error("Invalid offer account")]
+    InvalidOfferAccount,
+    // 26: 报价数量为 0 或两种代币相同
+    #[error("Invalid offer terms")]
+    InvalidOfferTerms,
+    // 27: 代币已暂停
+    #[error("Token is paused")]
+    TokenPaused,
+    // 28: 签名者不是代币配置中的管理员
+    #[error("Signer is not the token admin")]
+    InvalidAdmin,
+    // 29: 发售配置或购买记录 PDA 不正确
+    #[error("Invalid sale account")]
+    InvalidSaleAccount,
+    // 30: 国库账户与发售配置不一致
+    #[error("Invalid treasury account")]
+    InvalidTreasury,
+    // 31: 超出单个钱包的购买上限
+    #[error("Wallet purchase cap exceeded")]
+    WalletCapExceeded,
+    // 32: 缺少白名单账户或白名单 PDA 不正确
+    #[error("Invalid allowlist account")]
+    InvalidAllowlistAccount,
+    // 33: 超出白名单剩余额度
+    #[error("Allowlist allowance exceeded")]
+    AllowanceExceeded,
+    // 34: 质押池或用户质押 PDA 不正确
+    #[error("Invalid stake account")]
+    InvalidStakeAccount,
+    // 35: 解除质押数量超过已质押数量
+    #[error("Insufficient staked amount")]
+    InsufficientStake,
+    // 36: 多签门限或签名者数量无效（1 <= M <= N <= 11）
+    #[error("Invalid multisig configuration")]
+    InvalidMultisig,
+    // 37: 签名者不是冻结权限，或代币没有冻结权限
+    #[error("Invalid freeze authority")]
+    InvalidFreezeAuthority,
+    // 38: 委托人账户与指令参数或源代币账户记录的委托人不一致
+    #[error("Invalid delegate")]
+    InvalidDelegate,
+    // 39: 转账数量超过剩余委托额度
+    #[error("Delegated amount exceeded")]
+    DelegatedAmountExceeded,
+    // 40: 铸币账户不是代币程序对应的原生 SOL 铸币
+    #[error("Invalid native mint")]
+    InvalidNativeMint,
+    // 41: 算术运算溢出
+    #[error("Arithmetic overflow")]
+    ArithmeticOverflow,
+    // 42: 发售价格为 0 或国库账户为默认地址
+    #[error("Invalid sale terms")]
+    InvalidSaleTerms,
+}
+
+impl From<TokenError> for ProgramError {
+    fn from(e: TokenError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for TokenError {
+    fn type_of() -> &'static str {
+        "TokenError"
+    }
+}
+
+impl PrintProgramError for TokenError {
+    fn print<E>(&self)
+    where
+        E: 'static
+            + std::error::Error
+            + DecodeError<E>
+            + PrintProgramError
+            + num_traits::FromPrimitive,
+    {
+        msg!("Error: {}", self);
+    }
+}
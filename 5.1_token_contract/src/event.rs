@@ -0,0 +1,293 @@
+//! 结构化事件
+//!
+//! 处理器在每个操作成功后通过 `sol_log_data` 输出 Borsh 编码的 [`TokenEvent`]，
+//! 枚举的 Borsh 变体序号（首字节）即事件判别符。运行时将其记录为
+//! `Program data: <base64>` 日志，链下工具可用 [`parse_logs`] 从交易日志中还原事件。
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+use crate::instruction::AuthorityType;
+
+/// 代币程序输出的事件（新增事件只能追加在末尾，以保持判别符不变）
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, PartialEq, Eq)]
+pub enum TokenEvent {
+    // 创建代币
+    TokenCreated {
+        mint: Pubkey,
+        admin: Pubkey,
+        decimals: u8,
+        max_supply: u64,
+    },
+    // 铸造代币（Mint / BatchMint / Claim / RequestTokens）
+    Minted {
+        mint: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+    },
+    // 经由本程序的转账（Transfer / ClaimVested / 报价金库）
+    Transferred {
+        mint: Pubkey,
+        source: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+    },
+    // 销毁代币
+    Burned {
+        mint: Pubkey,
+        account: Pubkey,
+        amount: u64,
+    },
+    // 关闭代币账户
+    AccountClosed {
+        account: Pubkey,
+        destination: Pubkey,
+    },
+    // 冻结代币账户
+    AccountFrozen {
+        mint: Pubkey,
+        account: Pubkey,
+    },
+    // 解冻代币账户
+    AccountThawed {
+        mint: Pubkey,
+        account: Pubkey,
+    },
+    // 更新代币元数据
+    MetadataUpdated {
+        mint: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    // 创建归属计划
+    VestingCreated {
+        mint: Pubkey,
+        beneficiary: Pubkey,
+        total: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+    },
+    // 领取已解锁的代币
+    VestedClaimed {
+        mint: Pubkey,
+        beneficiary: Pubkey,
+        amount: u64,
+    },
+    // 发布默克尔空投
+    DistributorCreated {
+        mint: Pubkey,
+        merkle_root: [u8; 32],
+        num_nodes: u64,
+    },
+    // 领取空投
+    AirdropClaimed {
+        mint: Pubkey,
+        claimant: Pubkey,
+        index: u64,
+        amount: u64,
+    },
+    // 创建水龙头
+    FaucetCreated {
+        mint: Pubkey,
+        amount_per_request: u64,
+        cooldown_slots: u64,
+    },
+    // 从水龙头领取
+    FaucetRequested {
+        mint: Pubkey,
+        wallet: Pubkey,
+        amount: u64,
+        slot: u64,
+    },
+    // 挂出交换报价
+    OfferMade {
+        offer: Pubkey,
+        maker: Pubkey,
+        offered_mint: Pubkey,
+        offered_amount: u64,
+        wanted_mint: Pubkey,
+        wanted_amount: u64,
+    },
+    // 报价成交
+    OfferTaken {
+        offer: Pubkey,
+        taker: Pubkey,
+    },
+    // 撤销报价
+    OfferCancelled {
+        offer: Pubkey,
+    },
+    // 暂停 / 恢复代币
+    PauseUpdated {
+        mint: Pubkey,
+        paused: bool,
+    },
+    // 创建公开发售
+    SaleCreated {
+        mint: Pubkey,
+        price_lamports: u64,
+        treasury: Pubkey,
+        wallet_cap: Option<u64>,
+    },
+    // 购买代币
+    Purchased {
+        mint: Pubkey,
+        buyer: Pubkey,
+        amount: u64,
+        lamports: u64,
+    },
+    // 设置白名单额度
+    AllowlistUpdated {
+        mint: Pubkey,
+        wallet: Pubkey,
+        allowance: u64,
+    },
+    // 移出白名单
+    AllowlistRemoved {
+        mint: Pubkey,
+        wallet: Pubkey,
+    },
+    // 创建质押池
+    PoolCreated {
+        mint: Pubkey,
+        reward_rate: u64,
+    },
+    // 质押
+    Staked {
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    },
+    // 解除质押
+    Unstaked {
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    },
+    // 领取质押奖励
+    RewardsClaimed {
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    },
+    // 变更或撤销铸币 / 冻结权限
+    AuthorityChanged {
+        mint: Pubkey,
+        authority_type: AuthorityType,
+        new_authority: Option<Pubkey>,
+    },
+    // 授权委托额度
+    Approved {
+        mint: Pubkey,
+        account: Pubkey,
+        delegate: Pubkey,
+        amount: u64,
+    },
+    // 撤销委托
+    Revoked {
+        account: Pubkey,
+    },
+    // 包装 SOL 为 wSOL
+    SolWrapped {
+        owner: Pubkey,
+        account: Pubkey,
+        lamports: u64,
+    },
+    // 解包 wSOL（关闭 wSOL 账户）
+    SolUnwrapped {
+        owner: Pubkey,
+        account: Pubkey,
+        lamports: u64,
+    },
+}
+
+impl TokenEvent {
+    /// 事件名称（即枚举变体名）
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TokenCreated { .. } => "TokenCreated",
+            Self::Minted { .. } => "Minted",
+            Self::Transferred { .. } => "Transferred",
+            Self::Burned { .. } => "Burned",
+            Self::AccountClosed { .. } => "AccountClosed",
+            Self::AccountFrozen { .. } => "AccountFrozen",
+            Self::AccountThawed { .. } => "AccountThawed",
+            Self::MetadataUpdated { .. } => "MetadataUpdated",
+            Self::VestingCreated { .. } => "VestingCreated",
+            Self::VestedClaimed { .. } => "VestedClaimed",
+            Self::DistributorCreated { .. } => "DistributorCreated",
+            Self::AirdropClaimed { .. } => "AirdropClaimed",
+            Self::FaucetCreated { .. } => "FaucetCreated",
+            Self::FaucetRequested { .. } => "FaucetRequested",
+            Self::OfferMade { .. } => "OfferMade",
+            Self::OfferTaken { .. } => "OfferTaken",
+            Self::OfferCancelled { .. } => "OfferCancelled",
+            Self::PauseUpdated { .. } => "PauseUpdated",
+            Self::SaleCreated { .. } => "SaleCreated",
+            Self::Purchased { .. } => "Purchased",
+            Self::AllowlistUpdated { .. } => "AllowlistUpdated",
+            Self::AllowlistRemoved { .. } => "AllowlistRemoved",
+            Self::PoolCreated { .. } => "PoolCreated",
+            Self::Staked { .. } => "Staked",
+            Self::Unstaked { .. } => "Unstaked",
+            Self::RewardsClaimed { .. } => "RewardsClaimed",
+            Self::AuthorityChanged { .. } => "AuthorityChanged",
+            Self::Approved { .. } => "Approved",
+            Self::Revoked { .. } => "Revoked",
+            Self::SolWrapped { .. } => "SolWrapped",
+            Self::SolUnwrapped { .. } => "SolUnwrapped",
+        }
+    }
+}
+
+/// 通过 `sol_log_data` 输出事件
+pub fn emit(event: &TokenEvent) {
+    // 事件只包含定长字段与短字符串，序列化到 Vec 不会失败
+    let data = borsh::to_vec(event).unwrap();
+    sol_log_data(&[&data]);
+}
+
+/// 从 `sol_log_data` 输出的原始字节解码事件，无法解析时返回 None
+pub fn decode_event(data: &[u8]) -> Option<TokenEvent> {
+    TokenEvent::try_from_slice(data).ok()
+}
+
+/// 从交易日志中还原本程序输出的事件
+///
+/// 根据 `Program <id> invoke [n]` / `Program <id> success|failed` 跟踪调用栈，
+/// 只解码由 `program_id` 直接输出的 `Program data:` 日志（忽略 CPI 到其他程序时的输出）
+pub fn parse_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Vec<TokenEvent> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            // 每个 sol_log_data 调用只输出一段数据
+            if let Some(event) = STANDARD
+                .decode(data.trim())
+                .ok()
+                .and_then(|bytes| decode_event(&bytes))
+            {
+                events.push(event);
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(id), Some("invoke")) => stack.push(id),
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
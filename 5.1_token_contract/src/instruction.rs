@@ -1,8 +1,1233 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    sysvar,
+};
+use solana_system_interface::program as system_program;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use crate::{
+    find_allowlist_address, find_claim_bitmap_address, find_config_address,
+    find_distributor_address, find_faucet_address, find_faucet_claim_address,
+    find_metadata_address, find_mint_authority_address, find_offer_address, find_sale_address,
+    find_sale_purchase_address, find_stake_pool_address, find_user_stake_address,
+    find_vesting_address, native_mint_address,
+};
+
+/// Token-2022 转账手续费扩展参数
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy)]
+pub struct TransferFeeArgs {
+    pub transfer_fee_basis_points: u16, // 手续费率（万分比）
+    pub maximum_fee: u64,               // 单笔最大手续费
+}
+
+/// `SetAuthority` 可变更的权限类型
+#[derive(BorshDeserialize, BorshSerialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthorityType {
+    MintTokens,    // 铸币权限
+    FreezeAccount, // 冻结权限
+}
 
 // 为 TokenInstruction 实现 BorshDeserialize 和 BorshSerialize trait
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
 pub enum TokenInstruction {
-    CreateToken { decimals: u8 },
-    Mint { amount: u64 },
+    // 创建代币，可选冻结权限，并记录最大发行量和元数据；
+    // 代币程序可为 SPL Token 或 Token-2022，转账手续费扩展仅支持 Token-2022；
+    // multisig_threshold 为 Some(M) 时铸币权限为新建的 M-of-N 多签 PDA
+    CreateToken {
+        decimals: u8,
+        freeze_authority: Option<Pubkey>,
+        max_supply: u64,
+        transfer_fee: Option<TransferFeeArgs>,
+        name: String,
+        symbol: String,
+        uri: String,
+        multisig_threshold: Option<u8>,
+    },
+    // 铸造到接收者的 ATA，ATA 不存在时自动创建；
+    // 程序 PDA 铸币权限下非管理员调用者需提供白名单 PDA，并扣减其剩余额度；
+    // 多签铸币权限时在末尾传入签名者账户
+    Mint {
+        amount: u64,
+    },
+    // 批量铸造，接收者及其 ATA 作为剩余账户成对传入，多签签名者附在其后
+    BatchMint {
+        amounts: Vec<u64>,
+    },
+    // 转账（校验小数位数），目标 ATA 不存在时自动创建
+    Transfer {
+        amount: u64,
+        decimals: u8,
+    },
+    // 销毁代币（校验小数位数）
+    Burn {
+        amount: u64,
+    },
+    // 关闭代币账户，回收的租金转入指定账户
+    CloseAccount,
+    // 冻结代币账户（需要冻结权限签名）
+    FreezeAccount,
+    // 解冻代币账户（需要冻结权限签名）
+    ThawAccount,
+    // 更新代币元数据（需要铸币权限签名；多签铸币权限时在末尾传入 M 个签名者账户）
+    UpdateMetadata {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    // 创建线性归属计划，将 total 数量的代币锁入托管账户
+    CreateVesting {
+        beneficiary: Pubkey,
+        total: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+    },
+    // 受益人领取已解锁的代币
+    ClaimVested,
+    // 发布默克尔空投（叶子为 (index, claimant, amount)），需要管理员签名
+    CreateDistributor {
+        merkle_root: [u8; 32],
+        num_nodes: u64,
+    },
+    // 凭默克尔证明领取空投，由程序铸币权限 PDA 铸造到领取者的 ATA
+    Claim {
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    },
+    // 创建水龙头：每次领取数量与冷却时间（slot 数），需要管理员签名
+    CreateFaucet {
+        amount_per_request: u64,
+        cooldown_slots: u64,
+    },
+    // 从水龙头领取测试代币，由程序铸币权限 PDA 铸造，冷却期间拒绝
+    RequestTokens,
+    // 挂出交换报价：提供的代币锁入报价 PDA 的金库 ATA
+    MakeOffer {
+        offered_amount: u64,
+        wanted_mint: Pubkey,
+        wanted_amount: u64,
+    },
+    // 接受报价：接受方支付想要的代币，金库中的代币原子性地释放给接受方
+    TakeOffer,
+    // 撤销报价：金库中的代币退回做市方
+    CancelOffer,
+    // 暂停代币：拒绝铸造以及经由本程序的转账和销毁（需要管理员签名）
+    Pause,
+    // 恢复代币（需要管理员签名）
+    Unpause,
+    // 创建公开发售：每个完整代币的 SOL 价格、国库账户与可选的单钱包上限（需要管理员签名）
+    CreateSale {
+        price_lamports: u64,
+        treasury: Pubkey,
+        wallet_cap: Option<u64>,
+    },
+    // 购买代币：SOL 转入国库，同一指令内铸造到买家的 ATA
+    Buy {
+        amount: u64,
+    },
+    // 将钱包加入白名单并设置可铸造额度（已存在时覆盖额度，需要管理员签名）
+    AddToAllowlist {
+        wallet: Pubkey,
+        allowance: u64,
+    },
+    // 将钱包移出白名单，关闭白名单 PDA 并退回租金（需要管理员签名）
+    RemoveFromAllowlist,
+    // 创建质押池：每秒释放 reward_rate 奖励，按质押量比例分配（需要管理员签名）
+    InitPool {
+        reward_rate: u64,
+    },
+    // 质押代币到质押池金库
+    Stake {
+        amount: u64,
+    },
+    // 从质押池金库取回质押的代币
+    Unstake {
+        amount: u64,
+    },
+    // 领取质押奖励，由程序铸币权限 PDA 铸造
+    ClaimRewards,
+    // 变更铸币或冻结权限；new_authority 为 None 时永久撤销；
+    // 当前权限为多签账户时在末尾传入签名者账户
+    SetAuthority {
+        authority_type: AuthorityType,
+        new_authority: Option<Pubkey>,
+    },
+    // 授权委托人从源代币账户转出不超过 amount 的代币（approve_checked）
+    Approve {
+        delegate: Pubkey,
+        amount: u64,
+    },
+    // 撤销源代币账户的委托
+    Revoke,
+    // 由委托人签名，代替所有者转账（校验小数位数），目标 ATA 不存在时自动创建
+    TransferFrom {
+        amount: u64,
+        decimals: u8,
+    },
+    // 将 SOL 转入所有者的 wSOL 关联令牌账户并同步余额，ATA 不存在时自动创建
+    WrapSol {
+        lamports: u64,
+    },
+    // 关闭所有者的 wSOL 关联令牌账户，全部 lamports 退回所有者
+    UnwrapSol,
+}
+
+// ===== 指令构建函数 =====
+// 账户顺序与 Processor 中对应处理函数的「账户顺序要求」保持一致；
+// `token_program_id` 为 `spl_token::id()` 或 `spl_token_2022::id()`
+
+/// 铸币权限账户的元数据：程序 PDA 无需签名，其他铸币权限必须签名
+fn mint_authority_meta(program_id: &Pubkey, mint: &Pubkey, mint_authority: &Pubkey) -> AccountMeta {
+    let (mint_authority_pda, _) = find_mint_authority_address(program_id, mint);
+    AccountMeta::new_readonly(*mint_authority, mint_authority != &mint_authority_pda)
+}
+
+/// 构建 `CreateToken` 指令
+///
+/// `mint_authority` 可传入 `find_mint_authority_address(program_id, mint)` 以使用程序 PDA 作为铸币权限；
+/// `multisig` 为 `Some((m, signers))` 时 `mint_authority` 须传入 `find_multisig_address(program_id, mint)`，
+/// 由程序创建为 M-of-N 的 spl-token `Multisig` 并作为铸币权限
+#[allow(clippy::too_many_arguments)]
+pub fn create_token(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    decimals: u8,
+    freeze_authority: Option<Pubkey>,
+    max_supply: u64,
+    transfer_fee: Option<TransferFeeArgs>,
+    name: String,
+    symbol: String,
+    uri: String,
+    multisig: Option<(u8, &[Pubkey])>,
+) -> Instruction {
+    let (config, _) = find_config_address(program_id, mint);
+    let (metadata, _) = find_metadata_address(program_id, mint);
+    let mut accounts = vec![
+        AccountMeta::new(*mint, true),
+        match multisig {
+            Some(_) => AccountMeta::new(*mint_authority, false),
+            None => AccountMeta::new_readonly(*mint_authority, false),
+        },
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(config, false),
+        AccountMeta::new(metadata, false),
+    ];
+    if let Some((_, signers)) = multisig {
+        accounts.extend(
+            signers
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(*signer, false)),
+        );
+    }
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::CreateToken {
+            decimals,
+            freeze_authority,
+            max_supply,
+            transfer_fee,
+            name,
+            symbol,
+            uri,
+            multisig_threshold: multisig.map(|(m, _)| m),
+        },
+        accounts,
+    )
+}
+
+/// 构建 `Mint` 指令：铸造到 `recipient` 的关联令牌账户
+///
+/// 同时传入 `payer` 的白名单 PDA（管理员铸造或非程序 PDA 铸币权限时忽略）；
+/// 铸币权限为多签账户时，`multisig_signers` 为参与签名的 M 个签名者
+#[allow(clippy::too_many_arguments)]
+pub fn mint(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    recipient: &Pubkey,
+    payer: &Pubkey,
+    mint_authority: &Pubkey,
+    multisig_signers: &[Pubkey],
+    amount: u64,
+) -> Instruction {
+    let (config, _) = find_config_address(program_id, mint);
+    let authority_meta = if multisig_signers.is_empty() {
+        mint_authority_meta(program_id, mint, mint_authority)
+    } else {
+        AccountMeta::new_readonly(*mint_authority, false)
+    };
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(
+            get_associated_token_address_with_program_id(recipient, mint, token_program_id),
+            false,
+        ),
+        AccountMeta::new_readonly(*recipient, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        authority_meta,
+        AccountMeta::new_readonly(config, false),
+        AccountMeta::new(find_allowlist_address(program_id, mint, payer).0, false),
+    ];
+    accounts.extend(
+        multisig_signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+    Instruction::new_with_borsh(*program_id, &TokenInstruction::Mint { amount }, accounts)
+}
+
+/// 构建 `BatchMint` 指令：`recipients` 为 (接收者, 铸造数量) 列表
+pub fn batch_mint(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    payer: &Pubkey,
+    mint_authority: &Pubkey,
+    multisig_signers: &[Pubkey],
+    recipients: &[(Pubkey, u64)],
+) -> Instruction {
+    let (config, _) = find_config_address(program_id, mint);
+    let authority_meta = if multisig_signers.is_empty() {
+        mint_authority_meta(program_id, mint, mint_authority)
+    } else {
+        AccountMeta::new_readonly(*mint_authority, false)
+    };
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        authority_meta,
+        AccountMeta::new_readonly(config, false),
+    ];
+    for (recipient, _) in recipients {
+        accounts.push(AccountMeta::new_readonly(*recipient, false));
+        accounts.push(AccountMeta::new(
+            get_associated_token_address_with_program_id(recipient, mint, token_program_id),
+            false,
+        ));
+    }
+    accounts.extend(
+        multisig_signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+    let amounts = recipients.iter().map(|(_, amount)| *amount).collect();
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::BatchMint { amounts },
+        accounts,
+    )
+}
+
+/// 构建 `Transfer` 指令：从 `source` 转账到 `destination_owner` 的关联令牌账户
+#[allow(clippy::too_many_arguments)]
+pub fn transfer(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination_owner: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::Transfer { amount, decimals },
+        vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    destination_owner,
+                    mint,
+                    token_program_id,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*destination_owner, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+        ],
+    )
+}
+
+/// 构建 `Burn` 指令
+pub fn burn(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::Burn { amount },
+        vec![
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+        ],
+    )
+}
+
+/// 构建 `CloseAccount` 指令：租金退回 `destination`
+pub fn close_account(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::CloseAccount,
+        vec![
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+    )
+}
+
+/// 构建 `FreezeAccount` 指令
+pub fn freeze_account(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    freeze_authority: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::FreezeAccount,
+        vec![
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*freeze_authority, true),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+    )
+}
+
+/// 构建 `ThawAccount` 指令
+pub fn thaw_account(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    freeze_authority: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::ThawAccount,
+        vec![
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*freeze_authority, true),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+    )
+}
+
+/// 构建 `UpdateMetadata` 指令：`authority` 为铸币权限（程序 PDA 铸币权限时为管理员）
+///
+/// 铸币权限为多签账户时，`authority` 传入多签地址，`multisig_signers` 为参与签名的 M 个签名者
+pub fn update_metadata(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    multisig_signers: &[Pubkey],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Instruction {
+    let (metadata, _) = find_metadata_address(program_id, mint);
+    let (config, _) = find_config_address(program_id, mint);
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*authority, multisig_signers.is_empty()),
+        AccountMeta::new_readonly(config, false),
+    ];
+    accounts.extend(
+        multisig_signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::UpdateMetadata { name, symbol, uri },
+        accounts,
+    )
+}
+
+/// 构建 `CreateVesting` 指令：从 `funder_token_account` 转出 `total` 锁入归属托管账户
+#[allow(clippy::too_many_arguments)]
+pub fn create_vesting(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    funder: &Pubkey,
+    funder_token_account: &Pubkey,
+    beneficiary: &Pubkey,
+    total: u64,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+) -> Instruction {
+    let (vesting, _) = find_vesting_address(program_id, mint, beneficiary);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::CreateVesting {
+            beneficiary: *beneficiary,
+            total,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        },
+        vec![
+            AccountMeta::new(*funder, true),
+            AccountMeta::new(*funder_token_account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(&vesting, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+        ],
+    )
+}
+
+/// 构建 `ClaimVested` 指令：领取到受益人的关联令牌账户
+pub fn claim_vested(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    beneficiary: &Pubkey,
+) -> Instruction {
+    let (vesting, _) = find_vesting_address(program_id, mint, beneficiary);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::ClaimVested,
+        vec![
+            AccountMeta::new(*beneficiary, true),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(beneficiary, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(&vesting, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+        ],
+    )
+}
+
+/// 构建 `CreateDistributor` 指令：`admin` 为代币配置中的管理员
+pub fn create_distributor(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    admin: &Pubkey,
+    merkle_root: [u8; 32],
+    num_nodes: u64,
+) -> Instruction {
+    let (distributor, _) = find_distributor_address(program_id, mint);
+    let (bitmap, _) = find_claim_bitmap_address(program_id, &distributor);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::CreateDistributor {
+            merkle_root,
+            num_nodes,
+        },
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new(distributor, false),
+            AccountMeta::new(bitmap, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// 构建 `Claim` 指令：`proof` 可由 [`crate::merkle::MerkleTree::proof`] 生成
+pub fn claim(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    claimant: &Pubkey,
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let (distributor, _) = find_distributor_address(program_id, mint);
+    let (bitmap, _) = find_claim_bitmap_address(program_id, &distributor);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::Claim {
+            index,
+            amount,
+            proof,
+        },
+        vec![
+            AccountMeta::new(*claimant, true),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(claimant, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(distributor, false),
+            AccountMeta::new(bitmap, false),
+            AccountMeta::new_readonly(find_mint_authority_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+    )
+}
+
+/// 构建 `CreateFaucet` 指令：`admin` 为代币配置中的管理员
+pub fn create_faucet(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    admin: &Pubkey,
+    amount_per_request: u64,
+    cooldown_slots: u64,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::CreateFaucet {
+            amount_per_request,
+            cooldown_slots,
+        },
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new(find_faucet_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// 构建 `RequestTokens` 指令：铸造到 `requester` 的关联令牌账户
+pub fn request_tokens(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    requester: &Pubkey,
+) -> Instruction {
+    let (faucet, _) = find_faucet_address(program_id, mint);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::RequestTokens,
+        vec![
+            AccountMeta::new(*requester, true),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(requester, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(faucet, false),
+            AccountMeta::new(
+                find_faucet_claim_address(program_id, &faucet, requester).0,
+                false,
+            ),
+            AccountMeta::new_readonly(find_mint_authority_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+    )
+}
+
+/// 构建 `MakeOffer` 指令：从 `maker_token_account` 锁定 `offered_amount`
+#[allow(clippy::too_many_arguments)]
+pub fn make_offer(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    maker: &Pubkey,
+    maker_token_account: &Pubkey,
+    offered_mint: &Pubkey,
+    offered_amount: u64,
+    wanted_mint: &Pubkey,
+    wanted_amount: u64,
+) -> Instruction {
+    let (offer, _) = find_offer_address(program_id, maker, offered_mint, wanted_mint);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::MakeOffer {
+            offered_amount,
+            wanted_mint: *wanted_mint,
+            wanted_amount,
+        },
+        vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(*maker_token_account, false),
+            AccountMeta::new_readonly(*offered_mint, false),
+            AccountMeta::new_readonly(*wanted_mint, false),
+            AccountMeta::new(offer, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    &offer,
+                    offered_mint,
+                    token_program_id,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(find_config_address(program_id, offered_mint).0, false),
+        ],
+    )
+}
+
+/// 构建 `TakeOffer` 指令：接受方从 `taker_token_account` 支付想要的代币
+///
+/// 两种代币可以属于不同的代币程序（SPL Token / Token-2022）
+#[allow(clippy::too_many_arguments)]
+pub fn take_offer(
+    program_id: &Pubkey,
+    offered_token_program_id: &Pubkey,
+    wanted_token_program_id: &Pubkey,
+    taker: &Pubkey,
+    taker_token_account: &Pubkey,
+    maker: &Pubkey,
+    offered_mint: &Pubkey,
+    wanted_mint: &Pubkey,
+) -> Instruction {
+    let (offer, _) = find_offer_address(program_id, maker, offered_mint, wanted_mint);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::TakeOffer,
+        vec![
+            AccountMeta::new(*taker, true),
+            AccountMeta::new(*maker, false),
+            AccountMeta::new_readonly(*offered_mint, false),
+            AccountMeta::new_readonly(*wanted_mint, false),
+            AccountMeta::new(offer, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    &offer,
+                    offered_mint,
+                    offered_token_program_id,
+                ),
+                false,
+            ),
+            AccountMeta::new(*taker_token_account, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    taker,
+                    offered_mint,
+                    offered_token_program_id,
+                ),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    maker,
+                    wanted_mint,
+                    wanted_token_program_id,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*offered_token_program_id, false),
+            AccountMeta::new_readonly(*wanted_token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(find_config_address(program_id, offered_mint).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id, wanted_mint).0, false),
+        ],
+    )
+}
+
+/// 构建 `CancelOffer` 指令：锁定的代币退回 `maker_token_account`
+pub fn cancel_offer(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    maker: &Pubkey,
+    maker_token_account: &Pubkey,
+    offered_mint: &Pubkey,
+    wanted_mint: &Pubkey,
+) -> Instruction {
+    let (offer, _) = find_offer_address(program_id, maker, offered_mint, wanted_mint);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::CancelOffer,
+        vec![
+            AccountMeta::new(*maker, true),
+            AccountMeta::new(*maker_token_account, false),
+            AccountMeta::new_readonly(*offered_mint, false),
+            AccountMeta::new_readonly(*wanted_mint, false),
+            AccountMeta::new(offer, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    &offer,
+                    offered_mint,
+                    token_program_id,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+    )
+}
+
+/// 构建 `Pause` 指令：`admin` 为代币配置中的管理员
+pub fn pause(program_id: &Pubkey, mint: &Pubkey, admin: &Pubkey) -> Instruction {
+    set_paused(program_id, mint, admin, TokenInstruction::Pause)
+}
+
+/// 构建 `Unpause` 指令：`admin` 为代币配置中的管理员
+pub fn unpause(program_id: &Pubkey, mint: &Pubkey, admin: &Pubkey) -> Instruction {
+    set_paused(program_id, mint, admin, TokenInstruction::Unpause)
+}
+
+fn set_paused(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    admin: &Pubkey,
+    instruction: TokenInstruction,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(find_config_address(program_id, mint).0, false),
+        ],
+    )
+}
+
+/// 构建 `CreateSale` 指令：`admin` 为代币配置中的管理员
+pub fn create_sale(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    admin: &Pubkey,
+    price_lamports: u64,
+    treasury: &Pubkey,
+    wallet_cap: Option<u64>,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::CreateSale {
+            price_lamports,
+            treasury: *treasury,
+            wallet_cap,
+        },
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new(find_sale_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// 构建 `Buy` 指令：`treasury` 须与发售配置一致
+pub fn buy(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    buyer: &Pubkey,
+    treasury: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (sale, _) = find_sale_address(program_id, mint);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::Buy { amount },
+        vec![
+            AccountMeta::new(*buyer, true),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(buyer, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(sale, false),
+            AccountMeta::new(
+                find_sale_purchase_address(program_id, &sale, buyer).0,
+                false,
+            ),
+            AccountMeta::new(*treasury, false),
+            AccountMeta::new_readonly(find_mint_authority_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+    )
+}
+
+/// 构建 `AddToAllowlist` 指令：`admin` 为代币配置中的管理员
+pub fn add_to_allowlist(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    admin: &Pubkey,
+    wallet: &Pubkey,
+    allowance: u64,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::AddToAllowlist {
+            wallet: *wallet,
+            allowance,
+        },
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new(find_allowlist_address(program_id, mint, wallet).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// 构建 `RemoveFromAllowlist` 指令：白名单 PDA 的租金退回 `admin`
+pub fn remove_from_allowlist(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    admin: &Pubkey,
+    wallet: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::RemoveFromAllowlist,
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new(find_allowlist_address(program_id, mint, wallet).0, false),
+        ],
+    )
+}
+
+/// 构建 `InitPool` 指令：`admin` 为代币配置中的管理员
+pub fn init_pool(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    admin: &Pubkey,
+    reward_rate: u64,
+) -> Instruction {
+    let (pool, _) = find_stake_pool_address(program_id, mint);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::InitPool { reward_rate },
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(&pool, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+    )
+}
+
+/// 构建 `Stake` 指令：从 `owner_token_account` 质押 `amount`
+pub fn stake(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    owner_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (pool, _) = find_stake_pool_address(program_id, mint);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::Stake { amount },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(*owner_token_account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(find_user_stake_address(program_id, &pool, owner).0, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(&pool, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+        ],
+    )
+}
+
+/// 构建 `Unstake` 指令：取回的代币转入 `owner_token_account`
+pub fn unstake(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    owner_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (pool, _) = find_stake_pool_address(program_id, mint);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::Unstake { amount },
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*owner_token_account, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(find_user_stake_address(program_id, &pool, owner).0, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(&pool, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+    )
+}
+
+/// 构建 `ClaimRewards` 指令：奖励铸造到 `owner` 的关联令牌账户
+pub fn claim_rewards(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    let (pool, _) = find_stake_pool_address(program_id, mint);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::ClaimRewards,
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(owner, mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(find_user_stake_address(program_id, &pool, owner).0, false),
+            AccountMeta::new_readonly(find_mint_authority_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+    )
+}
+
+/// 构建 `SetAuthority` 指令
+///
+/// 账户布局取决于当前铸币 / 冻结权限 `current_authority`：
+/// - 普通密钥对：`[mint, current_authority (签名), token_program]`，`admin` 传 `None`
+/// - 程序铸币权限 PDA：`[mint, mint_authority_pda, token_program, config, admin (签名)]`，
+///   `admin` 传入代币配置中的管理员
+/// - 多签账户：`[mint, multisig, token_program, signer (签名)...]`，
+///   `multisig_signers` 为参与签名的 M 个签名者
+#[allow(clippy::too_many_arguments)]
+pub fn set_authority(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    mint: &Pubkey,
+    current_authority: &Pubkey,
+    admin: Option<&Pubkey>,
+    multisig_signers: &[Pubkey],
+    authority_type: AuthorityType,
+    new_authority: Option<Pubkey>,
+) -> Instruction {
+    let authority_meta = if multisig_signers.is_empty() {
+        mint_authority_meta(program_id, mint, current_authority)
+    } else {
+        AccountMeta::new_readonly(*current_authority, false)
+    };
+    let mut accounts = vec![
+        AccountMeta::new(*mint, false),
+        authority_meta,
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(admin) = admin {
+        accounts.push(AccountMeta::new_readonly(
+            find_config_address(program_id, mint).0,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(*admin, true));
+    }
+    accounts.extend(
+        multisig_signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::SetAuthority {
+            authority_type,
+            new_authority,
+        },
+        accounts,
+    )
+}
+
+/// 构建 `Approve` 指令
+pub fn approve(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    delegate: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::Approve {
+            delegate: *delegate,
+            amount,
+        },
+        vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*delegate, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+    )
+}
+
+/// 构建 `Revoke` 指令
+pub fn revoke(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    source: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::Revoke,
+        vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+    )
+}
+
+/// 构建 `TransferFrom` 指令：`delegate` 签名，从 `source` 转账到 `destination_owner` 的关联令牌账户
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_from(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination_owner: &Pubkey,
+    delegate: &Pubkey,
+    payer: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::TransferFrom { amount, decimals },
+        vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    destination_owner,
+                    mint,
+                    token_program_id,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(*destination_owner, false),
+            AccountMeta::new_readonly(*delegate, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(find_config_address(program_id, mint).0, false),
+        ],
+    )
+}
+
+/// 构建 `WrapSol` 指令：包装到 `owner` 的 wSOL 关联令牌账户
+pub fn wrap_sol(
+    program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    owner: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let native_mint = native_mint_address(token_program_id);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::WrapSol { lamports },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(owner, &native_mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(native_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+    )
+}
+
+/// 构建 `UnwrapSol` 指令：关闭 `owner` 的 wSOL 关联令牌账户
+pub fn unwrap_sol(program_id: &Pubkey, token_program_id: &Pubkey, owner: &Pubkey) -> Instruction {
+    let native_mint = native_mint_address(token_program_id);
+    Instruction::new_with_borsh(
+        *program_id,
+        &TokenInstruction::UnwrapSol,
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(owner, &native_mint, token_program_id),
+                false,
+            ),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+    )
 }
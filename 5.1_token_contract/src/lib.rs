@@ -1,18 +1,212 @@
-use solana_program::{
-    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, pubkey::Pubkey,
-};
+use solana_program::pubkey::Pubkey;
+#[cfg(not(feature = "no-entrypoint"))]
+use solana_program::{account_info::AccountInfo, entrypoint, entrypoint::ProgramResult};
 
-mod instruction;
-mod processor;
+pub mod error;
+pub mod event;
+pub mod instruction;
+pub mod merkle;
+pub mod processor;
+pub mod state;
 
-use crate::processor::Processor;
+/// 程序所属铸币权限 PDA 的种子前缀
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
 
+/// 计算铸币账户对应的程序铸币权限 PDA：["mint_authority", mint]
+///
+/// 以该 PDA 作为铸币权限创建代币后，只能通过本程序的 `Mint` 指令铸造
+pub fn find_mint_authority_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_AUTHORITY_SEED, mint.as_ref()], program_id)
+}
+
+/// 多签铸币权限 PDA 的种子前缀
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+
+/// 计算铸币账户对应的多签铸币权限地址：["multisig", mint]
+///
+/// 多签账户由本程序以 PDA 签名创建（所有者为代币程序），不存在可以单独签名的私钥
+pub fn find_multisig_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MULTISIG_SEED, mint.as_ref()], program_id)
+}
+
+/// 代币配置 PDA 的种子前缀
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// 计算铸币账户对应的代币配置 PDA：["config", mint]
+pub fn find_config_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED, mint.as_ref()], program_id)
+}
+
+/// 代币元数据 PDA 的种子前缀
+pub const METADATA_SEED: &[u8] = b"metadata";
+
+/// 计算铸币账户对应的代币元数据 PDA：["metadata", mint]
+pub fn find_metadata_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[METADATA_SEED, mint.as_ref()], program_id)
+}
+
+/// 归属计划 PDA 的种子前缀
+pub const VESTING_SEED: &[u8] = b"vesting";
+
+/// 计算受益人在某铸币账户下的归属计划 PDA：["vesting", mint, beneficiary]
+///
+/// 托管代币存放在该 PDA 的关联令牌账户中
+pub fn find_vesting_address(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    beneficiary: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VESTING_SEED, mint.as_ref(), beneficiary.as_ref()],
+        program_id,
+    )
+}
+
+/// 默克尔空投分发器 PDA 的种子前缀
+pub const DISTRIBUTOR_SEED: &[u8] = b"distributor";
+
+/// 计算铸币账户对应的空投分发器 PDA：["distributor", mint]
+pub fn find_distributor_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISTRIBUTOR_SEED, mint.as_ref()], program_id)
+}
+
+/// 空投领取位图 PDA 的种子前缀
+pub const CLAIM_BITMAP_SEED: &[u8] = b"claim_bitmap";
+
+/// 计算分发器对应的领取位图 PDA：["claim_bitmap", distributor]
+pub fn find_claim_bitmap_address(program_id: &Pubkey, distributor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAIM_BITMAP_SEED, distributor.as_ref()], program_id)
+}
+
+/// 水龙头配置 PDA 的种子前缀
+pub const FAUCET_SEED: &[u8] = b"faucet";
+
+/// 计算铸币账户对应的水龙头配置 PDA：["faucet", mint]
+pub fn find_faucet_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET_SEED, mint.as_ref()], program_id)
+}
+
+/// 水龙头领取记录 PDA 的种子前缀
+pub const FAUCET_CLAIM_SEED: &[u8] = b"faucet_claim";
+
+/// 计算钱包在某水龙头下的领取记录 PDA：["faucet_claim", faucet, wallet]
+pub fn find_faucet_claim_address(
+    program_id: &Pubkey,
+    faucet: &Pubkey,
+    wallet: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[FAUCET_CLAIM_SEED, faucet.as_ref(), wallet.as_ref()],
+        program_id,
+    )
+}
+
+/// 交换报价 PDA 的种子前缀
+pub const OFFER_SEED: &[u8] = b"offer";
+
+/// 计算做市方在某交易对上的报价 PDA：["offer", maker, offered_mint, wanted_mint]
+///
+/// 每个做市方在同一交易对上同时只能有一个未成交的报价；
+/// 锁定的代币存放在该 PDA 的关联令牌账户（金库）中
+pub fn find_offer_address(
+    program_id: &Pubkey,
+    maker: &Pubkey,
+    offered_mint: &Pubkey,
+    wanted_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            OFFER_SEED,
+            maker.as_ref(),
+            offered_mint.as_ref(),
+            wanted_mint.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// 公开发售配置 PDA 的种子前缀
+pub const SALE_SEED: &[u8] = b"sale";
+
+/// 计算铸币账户对应的公开发售配置 PDA：["sale", mint]
+pub fn find_sale_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SALE_SEED, mint.as_ref()], program_id)
+}
+
+/// 购买记录 PDA 的种子前缀
+pub const SALE_PURCHASE_SEED: &[u8] = b"sale_purchase";
+
+/// 计算钱包在某发售下的购买记录 PDA：["sale_purchase", sale, buyer]
+pub fn find_sale_purchase_address(
+    program_id: &Pubkey,
+    sale: &Pubkey,
+    buyer: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SALE_PURCHASE_SEED, sale.as_ref(), buyer.as_ref()],
+        program_id,
+    )
+}
+
+/// 白名单 PDA 的种子前缀
+pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
+
+/// 计算钱包在某铸币账户下的白名单 PDA：["allowlist", mint, wallet]
+pub fn find_allowlist_address(program_id: &Pubkey, mint: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ALLOWLIST_SEED, mint.as_ref(), wallet.as_ref()],
+        program_id,
+    )
+}
+
+/// 质押池 PDA 的种子前缀
+pub const STAKE_POOL_SEED: &[u8] = b"stake_pool";
+
+/// 计算铸币账户对应的质押池 PDA：["stake_pool", mint]
+///
+/// 质押的代币存放在该 PDA 的关联令牌账户（金库）中
+pub fn find_stake_pool_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_POOL_SEED, mint.as_ref()], program_id)
+}
+
+/// 用户质押 PDA 的种子前缀
+pub const USER_STAKE_SEED: &[u8] = b"stake";
+
+/// 计算用户在某质押池下的质押 PDA：["stake", pool, owner]
+pub fn find_user_stake_address(program_id: &Pubkey, pool: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[USER_STAKE_SEED, pool.as_ref(), owner.as_ref()],
+        program_id,
+    )
+}
+
+/// 代币程序对应的原生 SOL 铸币（SPL Token 与 Token-2022 的 wSOL 铸币地址不同）
+pub fn native_mint_address(token_program_id: &Pubkey) -> Pubkey {
+    if token_program_id == &spl_token_2022::id() {
+        spl_token_2022::native_mint::id()
+    } else {
+        spl_token::native_mint::id()
+    }
+}
+
+// 作为依赖引入时（启用 no-entrypoint 特性）不导出入口点
+#[cfg(not(feature = "no-entrypoint"))]
 entrypoint!(process_instruction);
 
+#[cfg(not(feature = "no-entrypoint"))]
 fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    Processor::process(program_id, accounts, instruction_data)
+    #[allow(deprecated)]
+    use solana_program::program_error::PrintProgramError;
+
+    if let Err(error) = processor::Processor::process(program_id, accounts, instruction_data) {
+        // 输出可读的错误信息
+        #[allow(deprecated)]
+        error.print::<error::TokenError>();
+        return Err(error);
+    }
+    Ok(())
 }
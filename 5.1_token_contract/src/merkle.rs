@@ -0,0 +1,110 @@
+//! 默克尔空投分发器使用的哈希工具
+//!
+//! 叶子节点为 `(index, claimant, amount)`，链上 `Claim` 指令与链下构建
+//! 默克尔树 / 证明使用同一套哈希规则：
+//! - 叶子哈希：sha256(0x00 || index(le) || claimant || amount(le))
+//! - 内部节点：sha256(0x01 || min(a, b) || max(a, b))（排序后拼接，证明无需记录左右方向）
+
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+/// 叶子哈希前缀（与内部节点区分，防止第二原像攻击）
+const LEAF_PREFIX: &[u8] = &[0];
+/// 内部节点哈希前缀
+const NODE_PREFIX: &[u8] = &[1];
+
+/// 计算叶子节点 `(index, claimant, amount)` 的哈希
+pub fn leaf_hash(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// 计算两个子节点的父节点哈希（子节点按字节序排序后拼接）
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// 校验 `leaf` 沿 `proof` 计算出的根是否等于 `root`
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+    &computed == root
+}
+
+/// 链下构建的默克尔树
+///
+/// 每层节点数为奇数时，最后一个节点直接提升到上一层（其证明中不包含该层）
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>, // 第 0 层为叶子，最后一层为根
+}
+
+impl MerkleTree {
+    /// 由叶子哈希构建默克尔树，`leaves` 为空时返回 `None`
+    pub fn new(leaves: Vec<[u8; 32]>) -> Option<Self> {
+        if leaves.is_empty() {
+            return None;
+        }
+
+        let mut layers = vec![leaves];
+        while layers.last().map_or(0, Vec::len) > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Some(Self { layers })
+    }
+
+    /// 由 `(claimant, amount)` 列表构建，下标即叶子的 `index`；`claims` 为空时返回 `None`
+    pub fn from_claims(claims: &[(Pubkey, u64)]) -> Option<Self> {
+        Self::new(
+            claims
+                .iter()
+                .enumerate()
+                .map(|(index, (claimant, amount))| leaf_hash(index as u64, claimant, *amount))
+                .collect(),
+        )
+    }
+
+    /// 叶子数量（即分发器的 `num_nodes`）
+    pub fn len(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    /// 树中没有叶子时返回 true（由 `new` 构建的树总是非空）
+    pub fn is_empty(&self) -> bool {
+        self.layers[0].is_empty()
+    }
+
+    /// 默克尔根
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// 生成第 `index` 个叶子的证明（自底向上的兄弟节点列表）
+    pub fn proof(&self, mut index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let sibling = index ^ 1;
+            if sibling < layer.len() {
+                proof.push(layer[sibling]);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
@@ -1,23 +1,62 @@
 use solana_program::{
     account_info::{AccountInfo, next_account_info},
-    entrypoint::ProgramResult,
-    msg,                              // 用于在程序执行过程中输出日志信息
-    program::{invoke, invoke_signed}, // 用于执行跨程序调用(CPI)
-    pubkey::Pubkey,                   // Solana 的公钥类型
-    system_instruction,               // 系统指令（如创建账户）
-    sysvar::{Sysvar, rent::Rent},     // 系统变量（如租金计算）
+    clock::Clock, // 时钟系统变量（获取当前时间戳）
+    entrypoint::{MAX_PERMITTED_DATA_INCREASE, ProgramResult},
+    msg,                                // 用于在程序执行过程中输出日志信息
+    program::{invoke, invoke_signed},   // 用于执行跨程序调用(CPI)
+    program_error::ProgramError,        // 程序错误类型
+    program_pack::Pack,                 // 定长账户状态（多签账户大小）
+    pubkey::Pubkey,                     // Solana 的公钥类型
+    sysvar::{self, Sysvar, rent::Rent}, // 系统变量（如租金计算）
 };
-use spl_token::{
-    instruction::{initialize_mint, mint_to}, // SPL Token 的初始化铸币和铸造指令
-    state::Mint,                             // SPL Token 的铸币账户状态
+use solana_system_interface::{
+    instruction as system_instruction, // 系统指令（如创建账户）
+    program as system_program,         // 系统程序 ID
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id; // 推导关联令牌账户地址
+// Token-2022 的指令构建函数同时接受旧版 SPL Token 与 Token-2022 的程序 ID
+use spl_token_2022::{
+    extension::{
+        ExtensionType,
+        StateWithExtensions, // 扩展类型与带扩展的账户状态解析
+        transfer_fee::instruction::initialize_transfer_fee_config, // 初始化转账手续费扩展
+    },
+    // SPL Token 的初始化铸币、铸造、转账、销毁、关闭、冻结、解冻账户、设置权限、委托和同步原生余额指令
+    instruction::{
+        AuthorityType as SplAuthorityType, approve_checked, burn_checked, close_account,
+        freeze_account, initialize_mint, initialize_multisig2, mint_to, revoke, set_authority,
+        sync_native, thaw_account, transfer_checked,
+    },
+    state::{Account, Mint, Multisig}, // SPL Token 的代币账户、铸币账户与多签账户状态
 };
 
-use std::str::FromStr;
-
-use borsh::BorshDeserialize; // Borsh 反序列化库
+use borsh::{BorshDeserialize, BorshSerialize}; // Borsh 序列化 / 反序列化库
 
-use crate::instruction::TokenInstruction; // 自定义指令枚举
-use solana_program::program_pack::Pack; // 用于获取账户数据大小的 trait
+use crate::error::TokenError; // 自定义错误
+use crate::event::{self, TokenEvent}; // 结构化事件
+use crate::instruction::{AuthorityType, TokenInstruction, TransferFeeArgs}; // 自定义指令枚举
+use crate::merkle; // 默克尔证明校验
+use crate::native_mint_address; // 原生 SOL（wSOL）铸币
+use crate::state::{
+    AllowlistEntry, FaucetClaim, FaucetConfig, MerkleDistributor, Offer, SaleConfig, SalePurchase,
+    StakePool, TokenConfig, TokenMetadata, UserStake, VestingSchedule,
+}; // 程序拥有的状态账户
+use crate::{ALLOWLIST_SEED, find_allowlist_address}; // 白名单 PDA
+use crate::{CLAIM_BITMAP_SEED, find_claim_bitmap_address}; // 空投领取位图 PDA
+use crate::{CONFIG_SEED, find_config_address}; // 代币配置 PDA
+use crate::{DISTRIBUTOR_SEED, find_distributor_address}; // 空投分发器 PDA
+use crate::{FAUCET_CLAIM_SEED, find_faucet_claim_address}; // 水龙头领取记录 PDA
+use crate::{FAUCET_SEED, find_faucet_address}; // 水龙头配置 PDA
+use crate::{METADATA_SEED, find_metadata_address}; // 代币元数据 PDA
+use crate::{MINT_AUTHORITY_SEED, find_mint_authority_address}; // 程序铸币权限 PDA
+use crate::{MULTISIG_SEED, find_multisig_address}; // 多签铸币权限 PDA
+use crate::{OFFER_SEED, find_offer_address}; // 交换报价 PDA
+use crate::{SALE_PURCHASE_SEED, find_sale_purchase_address}; // 购买记录 PDA
+use crate::{SALE_SEED, find_sale_address}; // 公开发售配置 PDA
+use crate::{STAKE_POOL_SEED, find_stake_pool_address}; // 质押池 PDA
+use crate::{USER_STAKE_SEED, find_user_stake_address}; // 用户质押 PDA
+use crate::{VESTING_SEED, find_vesting_address}; // 归属计划 PDA
+use solana_program::program_option::COption; // 铸币权限的可选类型
 
 /// 代币处理器结构体
 pub struct Processor;
@@ -26,14 +65,14 @@ impl Processor {
     /// 处理程序入口点
     ///
     /// # 参数
-    /// - `_program_id`: 当前程序的ID
+    /// - `program_id`: 当前程序的ID
     /// - `accounts`: 传入的账户列表
     /// - `instruction_data`: 指令数据
     ///
     /// # 返回
     /// 程序执行结果
     pub fn process(
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
         accounts: &[AccountInfo],
         instruction_data: &[u8],
     ) -> ProgramResult {
@@ -42,25 +81,170 @@ impl Processor {
 
         // 根据指令类型分发处理
         match instruction {
-            TokenInstruction::CreateToken { decimals } => Self::create_token(accounts, decimals),
-            TokenInstruction::Mint { amount } => Self::mint(accounts, amount),
+            TokenInstruction::CreateToken {
+                decimals,
+                freeze_authority,
+                max_supply,
+                transfer_fee,
+                name,
+                symbol,
+                uri,
+                multisig_threshold,
+            } => Self::create_token(
+                program_id,
+                accounts,
+                decimals,
+                freeze_authority,
+                max_supply,
+                transfer_fee,
+                name,
+                symbol,
+                uri,
+                multisig_threshold,
+            ),
+            TokenInstruction::Mint { amount } => Self::mint(program_id, accounts, amount),
+            TokenInstruction::BatchMint { amounts } => {
+                Self::batch_mint(program_id, accounts, amounts)
+            }
+            TokenInstruction::Transfer { amount, decimals } => {
+                Self::transfer(program_id, accounts, amount, decimals)
+            }
+            TokenInstruction::Burn { amount } => Self::burn(program_id, accounts, amount),
+            TokenInstruction::CloseAccount => Self::close_account(accounts),
+            TokenInstruction::FreezeAccount => Self::freeze_account(accounts),
+            TokenInstruction::ThawAccount => Self::thaw_account(accounts),
+            TokenInstruction::UpdateMetadata { name, symbol, uri } => {
+                Self::update_metadata(program_id, accounts, name, symbol, uri)
+            }
+            TokenInstruction::CreateVesting {
+                beneficiary,
+                total,
+                start_ts,
+                cliff_ts,
+                end_ts,
+            } => Self::create_vesting(
+                program_id,
+                accounts,
+                beneficiary,
+                total,
+                start_ts,
+                cliff_ts,
+                end_ts,
+            ),
+            TokenInstruction::ClaimVested => Self::claim_vested(program_id, accounts),
+            TokenInstruction::CreateDistributor {
+                merkle_root,
+                num_nodes,
+            } => Self::create_distributor(program_id, accounts, merkle_root, num_nodes),
+            TokenInstruction::Claim {
+                index,
+                amount,
+                proof,
+            } => Self::claim(program_id, accounts, index, amount, proof),
+            TokenInstruction::CreateFaucet {
+                amount_per_request,
+                cooldown_slots,
+            } => Self::create_faucet(program_id, accounts, amount_per_request, cooldown_slots),
+            TokenInstruction::RequestTokens => Self::request_tokens(program_id, accounts),
+            TokenInstruction::MakeOffer {
+                offered_amount,
+                wanted_mint,
+                wanted_amount,
+            } => Self::make_offer(
+                program_id,
+                accounts,
+                offered_amount,
+                wanted_mint,
+                wanted_amount,
+            ),
+            TokenInstruction::TakeOffer => Self::take_offer(program_id, accounts),
+            TokenInstruction::CancelOffer => Self::cancel_offer(program_id, accounts),
+            TokenInstruction::Pause => Self::set_paused(program_id, accounts, true),
+            TokenInstruction::Unpause => Self::set_paused(program_id, accounts, false),
+            TokenInstruction::CreateSale {
+                price_lamports,
+                treasury,
+                wallet_cap,
+            } => Self::create_sale(program_id, accounts, price_lamports, treasury, wallet_cap),
+            TokenInstruction::Buy { amount } => Self::buy(program_id, accounts, amount),
+            TokenInstruction::AddToAllowlist { wallet, allowance } => {
+                Self::add_to_allowlist(program_id, accounts, wallet, allowance)
+            }
+            TokenInstruction::RemoveFromAllowlist => {
+                Self::remove_from_allowlist(program_id, accounts)
+            }
+            TokenInstruction::InitPool { reward_rate } => {
+                Self::init_pool(program_id, accounts, reward_rate)
+            }
+            TokenInstruction::Stake { amount } => Self::stake(program_id, accounts, amount),
+            TokenInstruction::Unstake { amount } => Self::unstake(program_id, accounts, amount),
+            TokenInstruction::ClaimRewards => Self::claim_rewards(program_id, accounts),
+            TokenInstruction::SetAuthority {
+                authority_type,
+                new_authority,
+            } => Self::set_authority(program_id, accounts, authority_type, new_authority),
+            TokenInstruction::Approve { delegate, amount } => {
+                Self::approve(accounts, delegate, amount)
+            }
+            TokenInstruction::Revoke => Self::revoke(accounts),
+            TokenInstruction::TransferFrom { amount, decimals } => {
+                Self::transfer_from(program_id, accounts, amount, decimals)
+            }
+            TokenInstruction::WrapSol { lamports } => Self::wrap_sol(accounts, lamports),
+            TokenInstruction::UnwrapSol => Self::unwrap_sol(accounts),
         }
     }
 
     /// 创建代币（铸币账户）
     ///
+    /// 铸币权限账户传入程序 PDA ["mint_authority", mint] 时，
+    /// 铸造只能通过本程序的 `Mint` 指令（由程序 `invoke_signed` 签名）完成。
+    /// 同时创建代币配置 PDA ["config", mint]，记录管理员（支付账户）、小数位数和最大发行量。
+    ///
+    /// 代币程序账户可以是旧版 SPL Token 或 Token-2022；转账手续费扩展仅支持 Token-2022，
+    /// 其手续费配置权限与提取权限均为支付账户。
+    ///
+    /// 名称、符号和 URI 存放在本程序的元数据 PDA ["metadata", mint] 中（两种代币程序一致）
+    ///
+    /// `multisig_threshold` 为 `Some(M)` 时，铸币权限账户须是多签 PDA ["multisig", mint]，
+    /// 由本指令创建并初始化为 M-of-N 的 spl-token `Multisig`，N 个签名者作为剩余账户传入
+    ///
     /// # 参数
+    /// - `program_id`: 当前程序的ID
     /// - `accounts`: 传入的账户列表
     /// - `decimals`: 代币的小数位数
+    /// - `freeze_authority`: 冻结权限（为 `None` 时代币账户不可冻结）
+    /// - `max_supply`: 最大发行量（传 `u64::MAX` 表示不限量）
+    /// - `transfer_fee`: 转账手续费扩展参数（可选，仅 Token-2022）
+    /// - `name`: 代币名称
+    /// - `symbol`: 代币符号
+    /// - `uri`: 元数据 URI
+    /// - `multisig_threshold`: 多签门限 M（可选）
     ///
     /// # 账户顺序要求
-    /// 1. 铸币账户 (可写)
-    /// 2. 铸币权限账户 (签名)
-    /// 3. 支付账户 (签名)
+    /// 1. 铸币账户 (签名，可写)
+    /// 2. 铸币权限账户 (签名，或程序铸币权限 PDA；多签时为多签 PDA，可写)
+    /// 3. 支付账户 (签名，可写)
     /// 4. 租金系统变量账户 (只读)
     /// 5. 系统程序账户 (只读)
-    /// 6. 代币程序账户 (只读)
-    fn create_token(accounts: &[AccountInfo], decimals: u8) -> ProgramResult {
+    /// 6. 代币程序账户 (只读，SPL Token 或 Token-2022)
+    /// 7. 代币配置 PDA 账户 (可写)
+    /// 8. 代币元数据 PDA 账户 (可写)
+    ///
+    /// 多签时其后依次传入 N 个多签签名者账户 (只读)
+    #[allow(clippy::too_many_arguments)]
+    fn create_token(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        decimals: u8,
+        freeze_authority: Option<Pubkey>,
+        max_supply: u64,
+        transfer_fee: Option<TransferFeeArgs>,
+        name: String,
+        symbol: String,
+        uri: String,
+        multisig_threshold: Option<u8>,
+    ) -> ProgramResult {
         // 创建账户迭代器
         let accounts_iter = &mut accounts.iter();
 
@@ -71,27 +255,67 @@ impl Processor {
         let rent_sysvar = next_account_info(accounts_iter)?; // 租金系统变量
         let system_program = next_account_info(accounts_iter)?; // 系统程序
         let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let metadata_account = next_account_info(accounts_iter)?; // 代币元数据 PDA
+        let multisig_signers = accounts_iter.as_slice(); // 剩余账户：多签签名者
+
+        // ===== 账户验证 =====
+        check_signer(mint_account)?; // 新建铸币账户需要签名
+        check_writable(mint_account)?;
+        check_signer(payer)?;
+        check_writable(payer)?;
+        check_rent_sysvar(rent_sysvar)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_writable(config_account)?;
+        check_writable(metadata_account)?;
+
+        // 元数据字段长度检查
+        let metadata = TokenMetadata::new(*mint_account.key, name, symbol, uri);
+        if !metadata.is_valid() {
+            msg!("Metadata name, symbol or uri too long");
+            return Err(TokenError::MetadataTooLong.into());
+        }
 
         // 日志输出
         msg!("Creating mint account...");
         msg!("Mint: {}", mint_account.key);
 
+        // 铸币权限为程序 PDA 时，后续铸造都必须经过本程序
+        let (mint_authority_pda, _) = find_mint_authority_address(program_id, mint_account.key);
+        if mint_authority.key == &mint_authority_pda {
+            msg!("Mint authority is program PDA: {}", mint_authority_pda);
+        }
+
+        // 扩展只能用于 Token-2022
+        let mut extensions = Vec::new();
+        if transfer_fee.is_some() {
+            if token_program.key != &spl_token_2022::id() {
+                msg!("Transfer fee extension requires Token-2022 program");
+                return Err(TokenError::ExtensionRequiresToken2022.into());
+            }
+            extensions.push(ExtensionType::TransferFeeConfig);
+        }
+
+        // 计算铸币账户大小（基础 Mint 大小 + 扩展大小）
+        let mint_len = ExtensionType::try_calculate_account_len::<Mint>(&extensions)?;
+
         // 创建铸币账户 - 使用系统指令
         // 关键API: system_instruction::create_account
         // 功能: 创建一个新账户
         // 参数:
         //   payer.key - 支付账户
         //   mint_account.key - 新账户地址
-        //   Rent::get()?.minimum_balance(Mint::LEN) - 所需租金
-        //   Mint::LEN as u64 - 账户大小
+        //   Rent::get()?.minimum_balance(mint_len) - 所需租金
+        //   mint_len as u64 - 账户大小
         //   token_program.key - 账户所有者（代币程序）
         invoke(
             &system_instruction::create_account(
                 payer.key,
                 mint_account.key,
-                (Rent::get()?).minimum_balance(Mint::LEN), // 计算租金
-                Mint::LEN as u64,                          // 账户大小
-                token_program.key,                         // 账户所有者
+                (Rent::get()?).minimum_balance(mint_len), // 计算租金
+                mint_len as u64,                          // 账户大小
+                token_program.key,                        // 账户所有者
             ),
             &[
                 mint_account.clone(),
@@ -104,21 +328,65 @@ impl Processor {
         // 获取租金信息（虽然未使用，但展示了如何从账户获取租金）
         let _rent = Rent::from_account_info(rent_sysvar)?;
 
+        // 多签铸币权限：创建并初始化 M-of-N 多签账户
+        if let Some(m) = multisig_threshold {
+            create_multisig(
+                program_id,
+                mint_account.key,
+                payer,
+                mint_authority,
+                token_program,
+                system_program,
+                multisig_signers,
+                m,
+            )?;
+        }
+
+        // 扩展必须在 initialize_mint 之前初始化
+        if let Some(fee) = transfer_fee {
+            msg!(
+                "Initializing transfer fee: {} bps, max {}",
+                fee.transfer_fee_basis_points,
+                fee.maximum_fee
+            );
+
+            // 关键API: initialize_transfer_fee_config
+            // 功能: 初始化转账手续费扩展
+            // 参数:
+            //   token_program.key - 代币程序（Token-2022）
+            //   mint_account.key - 铸币账户
+            //   Some(payer.key) - 手续费配置权限
+            //   Some(payer.key) - 提取被扣留手续费的权限
+            //   transfer_fee_basis_points - 手续费率（万分比）
+            //   maximum_fee - 单笔最大手续费
+            invoke(
+                &initialize_transfer_fee_config(
+                    token_program.key,
+                    mint_account.key,
+                    Some(payer.key),
+                    Some(payer.key),
+                    fee.transfer_fee_basis_points,
+                    fee.maximum_fee,
+                )?,
+                &[mint_account.clone(), token_program.clone()],
+            )?;
+        }
+
         // 初始化铸币账户 - 使用SPL Token指令
         // 关键API: initialize_mint
         // 功能: 初始化铸币账户
         // 参数:
-        //   &spl_token::id() - 代币程序ID
+        //   token_program.key - 代币程序ID
         //   &mint_account.key - 铸币账户地址
         //   &mint_authority.key - 铸币权限
-        //   None - 冻结权限（无）
+        //   freeze_authority.as_ref() - 冻结权限（可选）
         //   decimals - 小数位数
         let ix = initialize_mint(
-            &spl_token::id(),
-            &mint_account.key,
-            &mint_authority.key,
-            None,     // 冻结权限
-            decimals, // 小数位数
+            token_program.key,
+            mint_account.key,
+            mint_authority.key,
+            freeze_authority.as_ref(), // 冻结权限
+            decimals,                  // 小数位数
         )?;
 
         // 日志输出
@@ -143,279 +411,4107 @@ impl Processor {
             &[], // 不需要额外签名
         )?;
 
+        // 创建代币配置 PDA 并写入最大发行量
+        let (config_pda, bump_seed) = find_config_address(program_id, mint_account.key);
+        if config_account.key != &config_pda {
+            msg!("Invalid config account: expected {}", config_pda);
+            return Err(TokenError::InvalidConfigAccount.into());
+        }
+        create_pda_account(
+            program_id,
+            payer,
+            config_account,
+            system_program,
+            TokenConfig::LEN,
+            &[CONFIG_SEED, mint_account.key.as_ref(), &[bump_seed]],
+        )?;
+        let config = TokenConfig::new(*payer.key, decimals, max_supply);
+        config.serialize(&mut &mut config_account.try_borrow_mut_data()?[..])?;
+        msg!("Token config created, max supply: {}", max_supply);
+
+        // 创建代币元数据 PDA 并写入名称、符号和 URI
+        let (metadata_pda, bump_seed) = find_metadata_address(program_id, mint_account.key);
+        if metadata_account.key != &metadata_pda {
+            msg!("Invalid metadata account: expected {}", metadata_pda);
+            return Err(TokenError::InvalidMetadataAccount.into());
+        }
+        create_pda_account(
+            program_id,
+            payer,
+            metadata_account,
+            system_program,
+            TokenMetadata::LEN,
+            &[METADATA_SEED, mint_account.key.as_ref(), &[bump_seed]],
+        )?;
+        metadata.serialize(&mut &mut metadata_account.try_borrow_mut_data()?[..])?;
+        msg!("Token metadata: {} ({})", metadata.name, metadata.symbol);
+
+        event::emit(&TokenEvent::TokenCreated {
+            mint: *mint_account.key,
+            admin: *payer.key,
+            decimals,
+            max_supply,
+        });
+
         // 成功日志
         msg!("SPL Token Mint created successfully");
 
         Ok(())
     }
 
-    /// 铸造代币到关联令牌账户
+    /// 铸造代币到接收者的关联令牌账户
+    ///
+    /// 接收者的关联令牌账户不存在时由支付账户出资创建。
+    /// 铸造后的发行量不得超过代币配置中的最大发行量；
+    /// 铸币权限为程序 PDA 时，支付账户须为代币配置中的管理员，
+    /// 或提供自己的白名单 PDA 并从其剩余额度中扣减本次铸造数量
     ///
     /// # 参数
+    /// - `program_id`: 当前程序的ID
     /// - `accounts`: 传入的账户列表
     /// - `amount`: 要铸造的代币数量
     ///
     /// # 账户顺序要求
     /// 1. 铸币账户 (可写)
-    /// 2. 关联令牌账户 (可写)
-    /// 3. 租金系统变量账户 (只读)
-    /// 4. 支付账户 (签名)
-    /// 5. 系统程序账户 (只读)
-    /// 6. 代币程序账户 (只读)
-    /// 7. 关联令牌账户程序 (只读)
-    pub fn mint(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    /// 2. 接收者关联令牌账户 (可写，必须等于 get_associated_token_address(recipient, mint))
+    /// 3. 接收者账户 (只读，关联令牌账户的所有者)
+    /// 4. 租金系统变量账户 (只读)
+    /// 5. 支付账户 (签名，可写)
+    /// 6. 系统程序账户 (只读)
+    /// 7. 代币程序账户 (只读)
+    /// 8. 关联令牌账户程序 (只读)
+    /// 9. 铸币权限账户 (签名，或程序铸币权限 PDA)
+    /// 10. 代币配置 PDA 账户 (只读)
+    /// 11. 支付账户的白名单 PDA 账户 (可写，可选；程序 PDA 铸币权限下非管理员必须提供)
+    ///
+    /// 铸币权限为多签账户时，其后依次传入参与签名的签名者账户 (签名)，作为 `mint_to` 的签名者集合
+    pub fn mint(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
         // 创建账户迭代器
         let accounts_iter = &mut accounts.iter();
 
         // 按顺序解析账户
         let mint_account = next_account_info(accounts_iter)?; // 铸币账户
-        let associated_token_account = next_account_info(accounts_iter)?; // 关联令牌账户
+        let associated_token_account = next_account_info(accounts_iter)?; // 接收者关联令牌账户
+        let recipient = next_account_info(accounts_iter)?; // 接收者账户
         let rent_sysvar = next_account_info(accounts_iter)?; // 租金系统变量
         let payer = next_account_info(accounts_iter)?; // 支付账户
         let system_program = next_account_info(accounts_iter)?; // 系统程序
         let token_program = next_account_info(accounts_iter)?; // 代币程序
         let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+        let mint_authority = next_account_info(accounts_iter)?; // 铸币权限账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let allowlist_account = next_account_info(accounts_iter).ok(); // 白名单 PDA（可选）
+        let multisig_signers = accounts_iter.as_slice(); // 剩余账户：多签签名者
 
-        // 调试输出
-        msg!("{:?}", associated_token_account);
+        // ===== 账户验证 =====
+        check_writable(mint_account)?;
+        check_writable(associated_token_account)?;
+        check_rent_sysvar(rent_sysvar)?;
+        check_signer(payer)?;
+        check_writable(payer)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_associated_token_address(
+            associated_token_account,
+            recipient.key,
+            mint_account.key,
+            token_program.key,
+        )?;
 
-        // 检查关联令牌账户是否存在（通过lamports判断）
-        if associated_token_account.lamports() == 0 {
-            // 如果不存在，创建关联令牌账户
-            msg!("Creating associated token account...");
+        // 检查调用者权限（白名单额度）与最大发行量
+        check_mint_allowed(
+            program_id,
+            mint_account,
+            mint_authority,
+            payer,
+            config_account,
+            allowlist_account,
+            amount,
+        )?;
 
-            // 关键API: spl_associated_token_account::instruction::create_associated_token_account
-            // 功能: 创建关联令牌账户指令
-            // 参数:
-            //   payer.key - 支付账户
-            //   payer.key - 代币所有者（此处与支付账户相同）
-            //   mint_account.key - 铸币账户
-            //   token_program.key - 代币程序
-            invoke(
-                &spl_associated_token_account::instruction::create_associated_token_account(
-                    payer.key,
-                    payer.key, // 代币所有者
-                    mint_account.key,
-                    token_program.key,
-                ),
-                &[
-                    payer.clone(),
-                    associated_token_account.clone(),
-                    mint_account.clone(),
-                    system_program.clone(),
-                    token_program.clone(),
-                    rent_sysvar.clone(),
-                    associated_token_program.clone(),
-                ],
-            )?;
-        } else {
-            msg!("Associated token account exists.");
-        }
+        // 接收者关联令牌账户不存在时创建
+        create_associated_token_account_if_missing(
+            payer,
+            associated_token_account,
+            recipient,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
 
         // 输出关联令牌账户地址
         msg!("Associated Token Address: {}", associated_token_account.key);
 
         // 铸造代币到关联令牌账户
-        msg!("Minting {} tokens to associated token account...", amount);
+        msg!("Minting {} tokens to {}...", amount, recipient.key);
+
+        mint_tokens(
+            program_id,
+            mint_account,
+            associated_token_account,
+            mint_authority,
+            token_program,
+            amount,
+            multisig_signers,
+        )?;
+
+        // 成功日志
+        msg!("Tokens minted to wallet successfully.");
+
+        Ok(())
+    }
+
+    /// 批量铸造代币到多个接收者的关联令牌账户（空投）
+    ///
+    /// 总铸造量一起参与最大发行量检查；接收者关联令牌账户不存在时由支付账户出资创建
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `amounts`: 每个接收者的铸造数量（与剩余账户对一一对应）
+    ///
+    /// # 账户顺序要求
+    /// 1. 铸币账户 (可写)
+    /// 2. 支付账户 (签名，可写)
+    /// 3. 系统程序账户 (只读)
+    /// 4. 代币程序账户 (只读)
+    /// 5. 关联令牌账户程序 (只读)
+    /// 6. 铸币权限账户 (签名，或程序铸币权限 PDA / 多签账户)
+    /// 7. 代币配置 PDA 账户 (只读)
+    ///
+    /// 剩余账户按对传入：接收者账户 (只读)、接收者关联令牌账户 (可写)；
+    /// 铸币权限为多签账户时，其后再依次传入参与签名的多签签名者账户 (签名)
+    pub fn batch_mint(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amounts: Vec<u64>,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let payer = next_account_info(accounts_iter)?; // 支付账户
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+        let mint_authority = next_account_info(accounts_iter)?; // 铸币权限账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let remaining_accounts = accounts_iter.as_slice(); // 剩余账户：接收者账户对与多签签名者
+
+        // ===== 账户验证 =====
+        check_writable(mint_account)?;
+        check_signer(payer)?;
+        check_writable(payer)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+
+        // 数量与账户对必须一一对应，只有多签铸币权限允许在账户对之后附加签名者
+        let pair_accounts = amounts.len() * 2;
+        if remaining_accounts.len() < pair_accounts
+            || (remaining_accounts.len() > pair_accounts
+                && unpack_multisig(mint_authority).is_none())
+        {
+            msg!(
+                "Expected {} recipient account pairs, got {} accounts",
+                amounts.len(),
+                remaining_accounts.len()
+            );
+            return Err(TokenError::BatchLengthMismatch.into());
+        }
+        let (recipient_accounts, multisig_signers) = remaining_accounts.split_at(pair_accounts);
+
+        // 总铸造量（溢出视为超过最大发行量）
+        let total = amounts
+            .iter()
+            .try_fold(0u64, |total, amount| total.checked_add(*amount))
+            .ok_or(TokenError::MaxSupplyExceeded)?;
+
+        // 检查调用者权限与最大发行量
+        check_mint_allowed(
+            program_id,
+            mint_account,
+            mint_authority,
+            payer,
+            config_account,
+            None, // 批量铸造仅限管理员
+            total,
+        )?;
+
+        msg!(
+            "Batch minting {} tokens to {} recipients...",
+            total,
+            amounts.len()
+        );
+
+        for (pair, amount) in recipient_accounts.chunks(2).zip(amounts) {
+            let recipient = &pair[0]; // 接收者账户
+            let associated_token_account = &pair[1]; // 接收者关联令牌账户
+
+            check_writable(associated_token_account)?;
+            check_associated_token_address(
+                associated_token_account,
+                recipient.key,
+                mint_account.key,
+                token_program.key,
+            )?;
+
+            // 接收者关联令牌账户不存在时创建
+            create_associated_token_account_if_missing(
+                payer,
+                associated_token_account,
+                recipient,
+                mint_account,
+                system_program,
+                token_program,
+                associated_token_program,
+            )?;
+
+            msg!("Minting {} tokens to {}...", amount, recipient.key);
+            mint_tokens(
+                program_id,
+                mint_account,
+                associated_token_account,
+                mint_authority,
+                token_program,
+                amount,
+                multisig_signers,
+            )?;
+        }
+
+        // 成功日志
+        msg!("Batch mint completed successfully.");
+
+        Ok(())
+    }
+
+    /// 在两个代币账户之间转账（校验小数位数），代币暂停期间拒绝
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `amount`: 转账数量
+    /// - `decimals`: 代币的小数位数（必须与铸币账户一致）
+    ///
+    /// # 账户顺序要求
+    /// 1. 源代币账户 (可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 目标关联令牌账户 (可写)
+    /// 4. 目标账户所有者 (只读)
+    /// 5. 源账户所有者 (签名)
+    /// 6. 支付账户 (签名，可写，用于创建目标关联令牌账户)
+    /// 7. 系统程序账户 (只读)
+    /// 8. 代币程序账户 (只读)
+    /// 9. 关联令牌账户程序 (只读)
+    /// 10. 代币配置 PDA 账户 (只读)
+    pub fn transfer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        decimals: u8,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let source_account = next_account_info(accounts_iter)?; // 源代币账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let destination_account = next_account_info(accounts_iter)?; // 目标关联令牌账户
+        let destination_owner = next_account_info(accounts_iter)?; // 目标账户所有者
+        let owner = next_account_info(accounts_iter)?; // 源账户所有者
+        let payer = next_account_info(accounts_iter)?; // 支付账户
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+
+        // ===== 账户验证 =====
+        check_writable(source_account)?;
+        check_writable(destination_account)?;
+        check_signer(owner)?;
+        check_signer(payer)?;
+        check_writable(payer)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_not_paused(program_id, config_account, mint_account.key)?;
+        check_associated_token_address(
+            destination_account,
+            destination_owner.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+
+        // 目标关联令牌账户不存在时创建
+        create_associated_token_account_if_missing(
+            payer,
+            destination_account,
+            destination_owner,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        msg!(
+            "Transferring {} tokens from {} to {}...",
+            amount,
+            source_account.key,
+            destination_account.key
+        );
+
+        // 源账户所有者已签名，无需 PDA 种子
+        transfer_tokens(
+            source_account,
+            mint_account,
+            destination_account,
+            owner,
+            token_program,
+            amount,
+            decimals,
+            &[],
+        )?;
+
+        // 成功日志
+        msg!("Tokens transferred successfully.");
+
+        Ok(())
+    }
+    /// 从代币账户中销毁代币（校验小数位数），代币暂停期间拒绝
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `amount`: 要销毁的代币数量
+    ///
+    /// # 账户顺序要求
+    /// 1. 代币账户 (可写)
+    /// 2. 铸币账户 (可写)
+    /// 3. 代币账户所有者 (签名)
+    /// 4. 代币程序账户 (只读)
+    /// 5. 代币配置 PDA 账户 (只读)
+    pub fn burn(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let token_account = next_account_info(accounts_iter)?; // 代币账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let owner = next_account_info(accounts_iter)?; // 代币账户所有者
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
 
-        // 关键API: mint_to
-        // 功能: 创建铸造代币指令
+        // ===== 账户验证 =====
+        check_writable(token_account)?;
+        check_writable(mint_account)?; // 销毁会减少铸币账户的供应量
+        check_signer(owner)?; // 所有者必须签名
+        check_token_program(token_program)?;
+        check_not_paused(program_id, config_account, mint_account.key)?;
+
+        // 从铸币账户读取小数位数，供 burn_checked 校验
+        let decimals = unpack_mint(mint_account)?.decimals;
+
+        msg!("Burning {} tokens from {}...", amount, token_account.key);
+
+        // 关键API: burn_checked
+        // 功能: 创建带小数位数校验的销毁指令
         // 参数:
         //   token_program.key - 代币程序
+        //   token_account.key - 代币账户
         //   mint_account.key - 铸币账户
-        //   associated_token_account.key - 目标账户
-        //   payer.key - 铸造权限
-        //   &[payer.key] - 签名者
-        //   amount - 铸造数量
+        //   owner.key - 代币账户所有者
+        //   &[] - 多签签名者（无）
+        //   amount - 销毁数量
+        //   decimals - 小数位数
         invoke(
-            &mint_to(
+            &burn_checked(
                 token_program.key,
+                token_account.key,
                 mint_account.key,
-                associated_token_account.key,
-                payer.key,    // 铸造权限
-                &[payer.key], // 签名者
+                owner.key,
+                &[],
                 amount,
+                decimals,
             )?,
             &[
+                token_account.clone(),
                 mint_account.clone(),
-                payer.clone(),
-                associated_token_account.clone(),
+                owner.clone(),
                 token_program.clone(),
             ],
         )?;
 
+        event::emit(&TokenEvent::Burned {
+            mint: *mint_account.key,
+            account: *token_account.key,
+            amount,
+        });
+
         // 成功日志
-        msg!("Tokens minted to wallet successfully.");
+        msg!("Tokens burned successfully.");
 
         Ok(())
     }
-}
 
-// use solana_program::{
-//     account_info::{AccountInfo, next_account_info},
-//     entrypoint::ProgramResult,
-//     msg,
-//     program::{invoke, invoke_signed},
-//     program_error::ProgramError,
-//     pubkey::Pubkey,
-//     system_instruction,
-//     sysvar::{self, Sysvar, rent::Rent},
-// };
-// use solana_sdk::system_program;
-// use spl_token::{instruction::initialize_mint, state::Mint};
-
-// use borsh::{BorshDeserialize, BorshSerialize};
-
-// use crate::instruction::TokenInstruction;
-
-// pub struct Processor;
-
-// impl Processor {
-//     pub fn process(
-//         _program_id: &Pubkey,
-//         accounts: &[AccountInfo],
-//         instruction_data: &[u8],
-//     ) -> ProgramResult {
-//         // 验证账户数量
-//         if accounts.len() < 6 {
-//             msg!("❌ 账户数量不足: 需要至少6个账户, 实际 {}", accounts.len());
-//             return Err(ProgramError::NotEnoughAccountKeys);
-//         }
-
-//         let instruction = TokenInstruction::try_from_slice(instruction_data)?;
-//         match instruction {
-//             TokenInstruction::CreateToken { decimals } => {
-//                 msg!("🏗️ 开始创建代币");
-//                 Self::create_token(accounts, decimals)
-//             }
-//             TokenInstruction::Mint { amount } => {
-//                 msg!("🪙 开始铸造代币");
-//                 Self::mint(accounts, amount)
-//             }
-//         }
-//     }
-
-//     fn create_token(accounts: &[AccountInfo], decimals: u8) -> ProgramResult {
-//         msg!("🔄 解析账户");
-//         let accounts_iter = &mut accounts.iter();
-
-//         // 解析账户
-//         let mint_account = next_account_info(accounts_iter)?;
-//         let mint_authority = next_account_info(accounts_iter)?;
-//         let payer = next_account_info(accounts_iter)?;
-//         let rent_sysvar = next_account_info(accounts_iter)?;
-//         let system_program = next_account_info(accounts_iter)?;
-//         let token_program = next_account_info(accounts_iter)?;
-
-//         // ===== 详细账户日志 =====
-//         msg!("📊 账户详细信息:");
-//         msg!("  1. 铸币账户: {}", mint_account.key);
-//         msg!("     所有者: {}", mint_account.owner);
-//         msg!("     余额: {} lamports", mint_account.lamports());
-//         msg!(
-//             "     可写: {}, 签名: {}",
-//             mint_account.is_writable,
-//             mint_account.is_signer
-//         );
-
-//         msg!("  2. 铸币权限: {}", mint_authority.key);
-//         msg!(
-//             "     可写: {}, 签名: {}",
-//             mint_authority.is_writable,
-//             mint_authority.is_signer
-//         );
-
-//         msg!("  3. 支付账户: {}", payer.key);
-//         msg!("     余额: {} lamports", payer.lamports());
-
-//         msg!("  4. 租金系统变量: {}", rent_sysvar.key);
-//         msg!("     所有者: {}", rent_sysvar.owner);
-//         msg!("     是系统变量: {}", rent_sysvar.owner == &sysvar::id());
-
-//         msg!("  5. 系统程序: {}", system_program.key);
-//         msg!("  6. 代币程序: {}", token_program.key);
-
-//         // ===== 账户验证 =====
-//         // 验证租金系统账户
-//         if rent_sysvar.key != &sysvar::rent::id() {
-//             msg!(
-//                 "❌ 无效的租金系统账户: 期望 {}, 实际 {}",
-//                 sysvar::rent::id(),
-//                 rent_sysvar.key
-//             );
-//             return Err(ProgramError::InvalidAccountData);
-//         }
-
-//         // 验证系统程序
-//         if system_program.key != &system_program::id() {
-//             msg!(
-//                 "❌ 无效的系统程序账户: 期望 {}, 实际 {}",
-//                 system_program::id(),
-//                 system_program.key
-//             );
-//             return Err(ProgramError::InvalidAccountData);
-//         }
-
-//         // 验证代币程序
-//         if token_program.key != &spl_token::id() {
-//             msg!(
-//                 "❌ 无效的代币程序账户: 期望 {}, 实际 {}",
-//                 spl_token::id(),
-//                 token_program.key
-//             );
-//             return Err(ProgramError::InvalidAccountData);
-//         }
-
-//         // ===== 创建铸币账户 =====
-//         msg!("🆕 创建铸币账户");
-
-//         // 方案1: 硬编码 Mint 长度 (82 字节)
-//         const MINT_LENGTH: usize = 82;
-
-//         // 方案2: 使用 borsh 序列化获取 Mint 长度
-//         // let mint_length = borsh::get_packed_len::<Mint>().unwrap_or(82);
-
-//         // 选择方案1
-//         let mint_length = MINT_LENGTH;
-
-//         let rent = Rent::get()?;
-//         let rent_amount = rent.minimum_balance(mint_length);
-//         msg!(
-//             "    租金要求: {} lamports (账户大小: {} 字节)",
-//             rent_amount,
-//             mint_length
-//         );
-
-//         invoke(
-//             &system_instruction::create_account(
-//                 payer.key,
-//                 mint_account.key,
-//                 rent_amount,
-//                 mint_length as u64,
-//                 token_program.key,
-//             ),
-//             &[payer.clone(), mint_account.clone(), system_program.clone()],
-//         )?;
-//         msg!("✅ 铸币账户创建成功");
-
-//         // ===== 初始化铸币账户 =====
-//         msg!("🔄 初始化铸币账户");
-//         let ix = initialize_mint(
-//             &spl_token::id(),
-//             mint_account.key,
-//             mint_authority.key,
-//             None,
-//             decimals,
-//         )?;
-
-//         invoke_signed(&ix, &[mint_account.clone(), rent_sysvar.clone()], &[])?;
-
-//         msg!("🎉 代币创建成功!");
-//         Ok(())
-//     }
-
-//     pub fn mint(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
-//         msg!("🪙 铸造 {} 代币", amount);
-//         Ok(())
-//     }
-// }
+    /// 关闭代币账户（余额必须为 0），回收租金
+    ///
+    /// # 参数
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 代币账户 (可写)
+    /// 2. 接收租金的目标账户 (可写)
+    /// 3. 代币账户所有者 (签名)
+    /// 4. 代币程序账户 (只读)
+    pub fn close_account(accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let token_account = next_account_info(accounts_iter)?; // 代币账户
+        let destination = next_account_info(accounts_iter)?; // 接收租金的目标账户
+        let owner = next_account_info(accounts_iter)?; // 代币账户所有者
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+
+        // ===== 账户验证 =====
+        check_signer(owner)?; // 所有者必须签名
+        check_token_program(token_program)?;
+
+        msg!(
+            "Closing token account {}, rent goes to {}...",
+            token_account.key,
+            destination.key
+        );
+
+        // 关键API: close_account
+        // 功能: 创建关闭代币账户指令
+        // 参数:
+        //   token_program.key - 代币程序
+        //   token_account.key - 待关闭的代币账户
+        //   destination.key - 接收租金的账户
+        //   owner.key - 代币账户所有者
+        //   &[] - 多签签名者（无）
+        invoke(
+            &close_account(
+                token_program.key,
+                token_account.key,
+                destination.key,
+                owner.key,
+                &[],
+            )?,
+            &[
+                token_account.clone(),
+                destination.clone(),
+                owner.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        event::emit(&TokenEvent::AccountClosed {
+            account: *token_account.key,
+            destination: *destination.key,
+        });
+
+        // 成功日志
+        msg!("Token account closed successfully.");
+
+        Ok(())
+    }
+    /// 冻结代币账户
+    ///
+    /// # 参数
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 代币账户 (可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 冻结权限账户 (签名)
+    /// 4. 代币程序账户 (只读)
+    pub fn freeze_account(accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let token_account = next_account_info(accounts_iter)?; // 代币账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let freeze_authority = next_account_info(accounts_iter)?; // 冻结权限账户
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+
+        // ===== 账户验证 =====
+        check_signer(freeze_authority)?; // 冻结权限必须签名
+        check_token_program(token_program)?;
+
+        msg!("Freezing token account {}...", token_account.key);
+
+        // 关键API: freeze_account
+        // 功能: 创建冻结代币账户指令
+        // 参数:
+        //   token_program.key - 代币程序
+        //   token_account.key - 待冻结的代币账户
+        //   mint_account.key - 铸币账户
+        //   freeze_authority.key - 冻结权限
+        //   &[] - 多签签名者（无）
+        invoke(
+            &freeze_account(
+                token_program.key,
+                token_account.key,
+                mint_account.key,
+                freeze_authority.key,
+                &[],
+            )?,
+            &[
+                token_account.clone(),
+                mint_account.clone(),
+                freeze_authority.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        event::emit(&TokenEvent::AccountFrozen {
+            mint: *mint_account.key,
+            account: *token_account.key,
+        });
+
+        // 成功日志
+        msg!("Token account frozen successfully.");
+
+        Ok(())
+    }
+
+    /// 解冻代币账户
+    ///
+    /// # 参数
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 代币账户 (可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 冻结权限账户 (签名)
+    /// 4. 代币程序账户 (只读)
+    pub fn thaw_account(accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let token_account = next_account_info(accounts_iter)?; // 代币账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let freeze_authority = next_account_info(accounts_iter)?; // 冻结权限账户
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+
+        // ===== 账户验证 =====
+        check_signer(freeze_authority)?; // 冻结权限必须签名
+        check_token_program(token_program)?;
+
+        msg!("Thawing token account {}...", token_account.key);
+
+        // 关键API: thaw_account
+        // 功能: 创建解冻代币账户指令
+        // 参数:
+        //   token_program.key - 代币程序
+        //   token_account.key - 待解冻的代币账户
+        //   mint_account.key - 铸币账户
+        //   freeze_authority.key - 冻结权限
+        //   &[] - 多签签名者（无）
+        invoke(
+            &thaw_account(
+                token_program.key,
+                token_account.key,
+                mint_account.key,
+                freeze_authority.key,
+                &[],
+            )?,
+            &[
+                token_account.clone(),
+                mint_account.clone(),
+                freeze_authority.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        event::emit(&TokenEvent::AccountThawed {
+            mint: *mint_account.key,
+            account: *token_account.key,
+        });
+
+        // 成功日志
+        msg!("Token account thawed successfully.");
+
+        Ok(())
+    }
+
+    /// 更新代币元数据（名称、符号、URI）
+    ///
+    /// 只有铸币权限可以调用；铸币权限为程序 PDA 时由代币配置中的管理员签名，
+    /// 为多签账户时由至少 M 个多签签名者签名
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `name`: 新名称
+    /// - `symbol`: 新符号
+    /// - `uri`: 新 URI
+    ///
+    /// # 账户顺序要求
+    /// 1. 代币元数据 PDA 账户 (可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 铸币权限账户 (签名；多签时为多签账户，只读)
+    /// 4. 代币配置 PDA 账户 (只读)
+    ///
+    /// 多签时其后依次传入参与签名的多签签名者账户 (签名)
+    pub fn update_metadata(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let metadata_account = next_account_info(accounts_iter)?; // 代币元数据 PDA
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let authority = next_account_info(accounts_iter)?; // 铸币权限账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let multisig_signers = accounts_iter.as_slice(); // 剩余账户：多签签名者
+
+        // ===== 账户验证 =====
+        check_writable(metadata_account)?;
+        check_mint_authority_signer(
+            program_id,
+            mint_account,
+            config_account,
+            authority,
+            multisig_signers,
+        )?;
+
+        let (metadata_pda, _) = find_metadata_address(program_id, mint_account.key);
+        if metadata_account.key != &metadata_pda || metadata_account.owner != program_id {
+            msg!("Invalid metadata account: expected {}", metadata_pda);
+            return Err(TokenError::InvalidMetadataAccount.into());
+        }
+
+        // 更新元数据
+        let metadata = TokenMetadata::new(*mint_account.key, name, symbol, uri);
+        if !metadata.is_valid() {
+            msg!("Metadata name, symbol or uri too long");
+            return Err(TokenError::MetadataTooLong.into());
+        }
+
+        // 账户按最大长度分配，先清零再写入，避免残留旧数据
+        let mut data = metadata_account.try_borrow_mut_data()?;
+        data.fill(0);
+        metadata.serialize(&mut &mut data[..])?;
+
+        event::emit(&TokenEvent::MetadataUpdated {
+            mint: *mint_account.key,
+            name: metadata.name.clone(),
+            symbol: metadata.symbol.clone(),
+            uri: metadata.uri.clone(),
+        });
+
+        // 成功日志
+        msg!(
+            "Token metadata updated: {} ({})",
+            metadata.name,
+            metadata.symbol
+        );
+
+        Ok(())
+    }
+
+    /// 创建线性归属计划（团队 / 贡献者锁仓）
+    ///
+    /// 资金方将 `total` 数量的代币转入归属计划 PDA ["vesting", mint, beneficiary]
+    /// 拥有的托管关联令牌账户，受益人之后通过 `ClaimVested` 按时间领取；代币暂停期间拒绝
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `beneficiary`: 受益人
+    /// - `total`: 锁定总量
+    /// - `start_ts` / `cliff_ts` / `end_ts`: 开始、悬崖、结束时间（Unix 时间戳）
+    ///
+    /// # 账户顺序要求
+    /// 1. 资金方账户 (签名，可写)
+    /// 2. 资金方代币账户 (可写)
+    /// 3. 铸币账户 (只读)
+    /// 4. 归属计划 PDA 账户 (可写)
+    /// 5. 托管关联令牌账户 (可写，归属计划 PDA 的 ATA)
+    /// 6. 系统程序账户 (只读)
+    /// 7. 代币程序账户 (只读)
+    /// 8. 关联令牌账户程序 (只读)
+    /// 9. 代币配置 PDA 账户 (只读)
+    pub fn create_vesting(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        beneficiary: Pubkey,
+        total: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let funder = next_account_info(accounts_iter)?; // 资金方账户
+        let funder_token_account = next_account_info(accounts_iter)?; // 资金方代币账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let vesting_account = next_account_info(accounts_iter)?; // 归属计划 PDA
+        let escrow_account = next_account_info(accounts_iter)?; // 托管关联令牌账户
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+
+        // ===== 账户验证 =====
+        check_signer(funder)?;
+        check_writable(funder)?;
+        check_writable(funder_token_account)?;
+        check_writable(vesting_account)?;
+        check_writable(escrow_account)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_not_paused(program_id, config_account, mint_account.key)?;
+
+        // 归属计划参数检查
+        let schedule = VestingSchedule::new(
+            *mint_account.key,
+            beneficiary,
+            total,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        );
+        if !schedule.is_valid() {
+            msg!(
+                "Invalid vesting schedule: start {}, cliff {}, end {}, total {}",
+                schedule.start_ts,
+                schedule.cliff_ts,
+                schedule.end_ts,
+                schedule.total
+            );
+            return Err(TokenError::InvalidVestingSchedule.into());
+        }
+
+        let (vesting_pda, bump_seed) =
+            find_vesting_address(program_id, mint_account.key, &schedule.beneficiary);
+        if vesting_account.key != &vesting_pda {
+            msg!("Invalid vesting account: expected {}", vesting_pda);
+            return Err(TokenError::InvalidVestingAccount.into());
+        }
+        check_associated_token_address(
+            escrow_account,
+            vesting_account.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+
+        // 创建归属计划 PDA 并写入计划
+        create_pda_account(
+            program_id,
+            funder,
+            vesting_account,
+            system_program,
+            VestingSchedule::LEN,
+            &[
+                VESTING_SEED,
+                mint_account.key.as_ref(),
+                schedule.beneficiary.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+        schedule.serialize(&mut &mut vesting_account.try_borrow_mut_data()?[..])?;
+
+        // 托管关联令牌账户不存在时创建（所有者为归属计划 PDA）
+        create_associated_token_account_if_missing(
+            funder,
+            escrow_account,
+            vesting_account,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        // 将锁定总量转入托管账户
+        msg!(
+            "Locking {} tokens for {}...",
+            schedule.total,
+            schedule.beneficiary
+        );
+        let decimals = unpack_mint(mint_account)?.decimals;
+        transfer_tokens(
+            funder_token_account,
+            mint_account,
+            escrow_account,
+            funder,
+            token_program,
+            schedule.total,
+            decimals,
+            &[],
+        )?;
+
+        event::emit(&TokenEvent::VestingCreated {
+            mint: *mint_account.key,
+            beneficiary,
+            total,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        });
+
+        // 成功日志
+        msg!("Vesting schedule created successfully.");
+
+        Ok(())
+    }
+
+    /// 受益人领取已解锁的代币
+    ///
+    /// 可领取数量 = 按 `Clock::get()` 当前时间计算的已解锁量 - 已领取量，
+    /// 由归属计划 PDA 签名从托管账户转出；代币暂停期间拒绝
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 受益人账户 (签名，可写)
+    /// 2. 受益人关联令牌账户 (可写，不存在时自动创建)
+    /// 3. 铸币账户 (只读)
+    /// 4. 归属计划 PDA 账户 (可写)
+    /// 5. 托管关联令牌账户 (可写)
+    /// 6. 系统程序账户 (只读)
+    /// 7. 代币程序账户 (只读)
+    /// 8. 关联令牌账户程序 (只读)
+    /// 9. 代币配置 PDA 账户 (只读)
+    pub fn claim_vested(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let beneficiary = next_account_info(accounts_iter)?; // 受益人账户
+        let beneficiary_token_account = next_account_info(accounts_iter)?; // 受益人关联令牌账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let vesting_account = next_account_info(accounts_iter)?; // 归属计划 PDA
+        let escrow_account = next_account_info(accounts_iter)?; // 托管关联令牌账户
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+
+        // ===== 账户验证 =====
+        check_signer(beneficiary)?;
+        check_writable(beneficiary)?;
+        check_writable(beneficiary_token_account)?;
+        check_writable(vesting_account)?;
+        check_writable(escrow_account)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_not_paused(program_id, config_account, mint_account.key)?;
+
+        let (vesting_pda, bump_seed) =
+            find_vesting_address(program_id, mint_account.key, beneficiary.key);
+        if vesting_account.key != &vesting_pda || vesting_account.owner != program_id {
+            msg!("Invalid vesting account: expected {}", vesting_pda);
+            return Err(TokenError::InvalidVestingAccount.into());
+        }
+        check_associated_token_address(
+            escrow_account,
+            vesting_account.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+        check_associated_token_address(
+            beneficiary_token_account,
+            beneficiary.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+
+        // 计算可领取数量
+        let mut schedule = VestingSchedule::try_from_slice(&vesting_account.data.borrow())?;
+        let now = Clock::get()?.unix_timestamp;
+        let amount = schedule.claimable_amount(now);
+        if amount == 0 {
+            msg!("Nothing to claim at {}", now);
+            return Err(TokenError::NothingToClaim.into());
+        }
+
+        // 受益人关联令牌账户不存在时创建
+        create_associated_token_account_if_missing(
+            beneficiary,
+            beneficiary_token_account,
+            beneficiary,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        // 由归属计划 PDA 签名，从托管账户转出
+        msg!("Claiming {} vested tokens...", amount);
+        let decimals = unpack_mint(mint_account)?.decimals;
+        transfer_tokens(
+            escrow_account,
+            mint_account,
+            beneficiary_token_account,
+            vesting_account,
+            token_program,
+            amount,
+            decimals,
+            &[&[
+                VESTING_SEED,
+                mint_account.key.as_ref(),
+                beneficiary.key.as_ref(),
+                &[bump_seed],
+            ]],
+        )?;
+
+        // 更新已领取数量
+        schedule.claimed = schedule
+            .claimed
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        schedule.serialize(&mut &mut vesting_account.try_borrow_mut_data()?[..])?;
+
+        event::emit(&TokenEvent::VestedClaimed {
+            mint: *mint_account.key,
+            beneficiary: *beneficiary.key,
+            amount,
+        });
+
+        // 成功日志
+        msg!(
+            "Vested tokens claimed: {} / {}",
+            schedule.claimed,
+            schedule.total
+        );
+
+        Ok(())
+    }
+
+    /// 发布默克尔空投分发器
+    ///
+    /// 创建分发器 PDA ["distributor", mint] 与领取位图 PDA ["claim_bitmap", distributor]，
+    /// 之后由领取者自行调用 `Claim`，避免管理员为成千上万个钱包批量铸造。
+    /// 铸币权限必须是程序 PDA，且签名者必须是代币配置中的管理员
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `merkle_root`: 叶子 (index, claimant, amount) 的默克尔根
+    /// - `num_nodes`: 叶子数量
+    ///
+    /// # 账户顺序要求
+    /// 1. 管理员账户 (签名，可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 代币配置 PDA 账户 (只读)
+    /// 4. 分发器 PDA 账户 (可写)
+    /// 5. 领取位图 PDA 账户 (可写)
+    /// 6. 系统程序账户 (只读)
+    pub fn create_distributor(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        merkle_root: [u8; 32],
+        num_nodes: u64,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let admin = next_account_info(accounts_iter)?; // 管理员账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let distributor_account = next_account_info(accounts_iter)?; // 分发器 PDA
+        let bitmap_account = next_account_info(accounts_iter)?; // 领取位图 PDA
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+
+        // ===== 账户验证 =====
+        check_writable(admin)?;
+        check_writable(distributor_account)?;
+        check_writable(bitmap_account)?;
+        check_system_program(system_program)?;
+        check_program_mint_authority(program_id, mint_account)?;
+        check_mint_authority_signer(program_id, mint_account, config_account, admin, &[])?;
+
+        // 叶子数量检查：位图账户通过 CPI 创建，大小受 MAX_PERMITTED_DATA_INCREASE 限制
+        let bitmap_len = MerkleDistributor::bitmap_len(num_nodes);
+        if num_nodes == 0 || bitmap_len > MAX_PERMITTED_DATA_INCREASE {
+            msg!("Invalid node count {}", num_nodes);
+            return Err(TokenError::InvalidNodeCount.into());
+        }
+
+        let (distributor_pda, distributor_bump) =
+            find_distributor_address(program_id, mint_account.key);
+        let (bitmap_pda, bitmap_bump) = find_claim_bitmap_address(program_id, &distributor_pda);
+        if distributor_account.key != &distributor_pda || bitmap_account.key != &bitmap_pda {
+            msg!(
+                "Invalid distributor accounts: expected {} / {}",
+                distributor_pda,
+                bitmap_pda
+            );
+            return Err(TokenError::InvalidDistributorAccount.into());
+        }
+
+        // 创建分发器 PDA 并写入默克尔根
+        msg!("Creating distributor for {} claims...", num_nodes);
+        create_pda_account(
+            program_id,
+            admin,
+            distributor_account,
+            system_program,
+            MerkleDistributor::LEN,
+            &[
+                DISTRIBUTOR_SEED,
+                mint_account.key.as_ref(),
+                &[distributor_bump],
+            ],
+        )?;
+        let distributor =
+            MerkleDistributor::new(*mint_account.key, *admin.key, merkle_root, num_nodes);
+        distributor.serialize(&mut &mut distributor_account.try_borrow_mut_data()?[..])?;
+
+        // 创建领取位图 PDA（新账户数据全为 0，即均未领取）
+        create_pda_account(
+            program_id,
+            admin,
+            bitmap_account,
+            system_program,
+            bitmap_len,
+            &[
+                CLAIM_BITMAP_SEED,
+                distributor_account.key.as_ref(),
+                &[bitmap_bump],
+            ],
+        )?;
+
+        event::emit(&TokenEvent::DistributorCreated {
+            mint: *mint_account.key,
+            merkle_root,
+            num_nodes,
+        });
+
+        // 成功日志
+        msg!("Distributor created successfully.");
+
+        Ok(())
+    }
+
+    /// 凭默克尔证明领取空投
+    ///
+    /// 校验叶子 (index, claimant, amount) 属于分发器的默克尔根，
+    /// 在位图中标记该下标后，由程序铸币权限 PDA 铸造到领取者的 ATA
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `index`: 叶子下标
+    /// - `amount`: 领取数量
+    /// - `proof`: 默克尔证明（自底向上的兄弟节点）
+    ///
+    /// # 账户顺序要求
+    /// 1. 领取者账户 (签名，可写)
+    /// 2. 领取者关联令牌账户 (可写，不存在时自动创建)
+    /// 3. 铸币账户 (可写)
+    /// 4. 分发器 PDA 账户 (可写)
+    /// 5. 领取位图 PDA 账户 (可写)
+    /// 6. 程序铸币权限 PDA 账户 (只读)
+    /// 7. 代币配置 PDA 账户 (只读)
+    /// 8. 系统程序账户 (只读)
+    /// 9. 代币程序账户 (只读)
+    /// 10. 关联令牌账户程序 (只读)
+    pub fn claim(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let claimant = next_account_info(accounts_iter)?; // 领取者账户
+        let claimant_token_account = next_account_info(accounts_iter)?; // 领取者关联令牌账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let distributor_account = next_account_info(accounts_iter)?; // 分发器 PDA
+        let bitmap_account = next_account_info(accounts_iter)?; // 领取位图 PDA
+        let mint_authority = next_account_info(accounts_iter)?; // 程序铸币权限 PDA
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+
+        // ===== 账户验证 =====
+        check_signer(claimant)?;
+        check_writable(claimant)?;
+        check_writable(claimant_token_account)?;
+        check_writable(mint_account)?;
+        check_writable(distributor_account)?;
+        check_writable(bitmap_account)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_associated_token_address(
+            claimant_token_account,
+            claimant.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+        check_program_mint_authority(program_id, mint_account)?;
+
+        let (distributor_pda, _) = find_distributor_address(program_id, mint_account.key);
+        let (bitmap_pda, _) = find_claim_bitmap_address(program_id, &distributor_pda);
+        if distributor_account.key != &distributor_pda
+            || distributor_account.owner != program_id
+            || bitmap_account.key != &bitmap_pda
+            || bitmap_account.owner != program_id
+        {
+            msg!(
+                "Invalid distributor accounts: expected {} / {}",
+                distributor_pda,
+                bitmap_pda
+            );
+            return Err(TokenError::InvalidDistributorAccount.into());
+        }
+        let mut distributor =
+            MerkleDistributor::try_from_slice(&distributor_account.data.borrow())?;
+        if index >= distributor.num_nodes {
+            msg!(
+                "Claim index {} out of range ({} nodes)",
+                index,
+                distributor.num_nodes
+            );
+            return Err(TokenError::ClaimIndexOutOfRange.into());
+        }
+
+        // 校验默克尔证明
+        let leaf = merkle::leaf_hash(index, claimant.key, amount);
+        if !merkle::verify(&proof, &distributor.merkle_root, leaf) {
+            msg!("Invalid merkle proof for index {}", index);
+            return Err(TokenError::InvalidMerkleProof.into());
+        }
+
+        // 检查并标记领取位图
+        {
+            let mut bitmap = bitmap_account.try_borrow_mut_data()?;
+            let byte = &mut bitmap[(index / 8) as usize];
+            let mask = 1u8 << (index % 8);
+            if *byte & mask != 0 {
+                msg!("Index {} already claimed", index);
+                return Err(TokenError::AlreadyClaimed.into());
+            }
+            *byte |= mask;
+        }
+
+        // 检查暂停状态与最大发行量
+        let config = load_config(program_id, config_account, mint_account.key)?;
+        check_can_mint(&config, mint_account, amount)?;
+
+        // 领取者关联令牌账户不存在时创建
+        create_associated_token_account_if_missing(
+            claimant,
+            claimant_token_account,
+            claimant,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        // 由程序铸币权限 PDA 铸造
+        msg!("Claiming {} tokens for index {}...", amount, index);
+        mint_tokens(
+            program_id,
+            mint_account,
+            claimant_token_account,
+            mint_authority,
+            token_program,
+            amount,
+            &[],
+        )?;
+
+        // 更新分发器统计
+        distributor.num_claimed = distributor
+            .num_claimed
+            .checked_add(1)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        distributor.total_claimed = distributor
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        distributor.serialize(&mut &mut distributor_account.try_borrow_mut_data()?[..])?;
+
+        event::emit(&TokenEvent::AirdropClaimed {
+            mint: *mint_account.key,
+            claimant: *claimant.key,
+            index,
+            amount,
+        });
+
+        // 成功日志
+        msg!("Airdrop claimed successfully.");
+
+        Ok(())
+    }
+
+    /// 创建水龙头（测试网络发放测试代币）
+    ///
+    /// 铸币权限必须是程序 PDA，且签名者必须是代币配置中的管理员
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `amount_per_request`: 每次领取数量
+    /// - `cooldown_slots`: 同一钱包两次领取之间的冷却时间（slot 数）
+    ///
+    /// # 账户顺序要求
+    /// 1. 管理员账户 (签名，可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 代币配置 PDA 账户 (只读)
+    /// 4. 水龙头配置 PDA 账户 (可写)
+    /// 5. 系统程序账户 (只读)
+    pub fn create_faucet(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount_per_request: u64,
+        cooldown_slots: u64,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let admin = next_account_info(accounts_iter)?; // 管理员账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let faucet_account = next_account_info(accounts_iter)?; // 水龙头配置 PDA
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+
+        // ===== 账户验证 =====
+        check_writable(admin)?;
+        check_writable(faucet_account)?;
+        check_system_program(system_program)?;
+        check_program_mint_authority(program_id, mint_account)?;
+        check_mint_authority_signer(program_id, mint_account, config_account, admin, &[])?;
+
+        let (faucet_pda, bump_seed) = find_faucet_address(program_id, mint_account.key);
+        if faucet_account.key != &faucet_pda {
+            msg!("Invalid faucet account: expected {}", faucet_pda);
+            return Err(TokenError::InvalidFaucetAccount.into());
+        }
+
+        // 创建水龙头配置 PDA 并写入配置
+        msg!(
+            "Creating faucet: {} per request, cooldown {} slots...",
+            amount_per_request,
+            cooldown_slots
+        );
+        create_pda_account(
+            program_id,
+            admin,
+            faucet_account,
+            system_program,
+            FaucetConfig::LEN,
+            &[FAUCET_SEED, mint_account.key.as_ref(), &[bump_seed]],
+        )?;
+        let faucet = FaucetConfig::new(*mint_account.key, amount_per_request, cooldown_slots);
+        faucet.serialize(&mut &mut faucet_account.try_borrow_mut_data()?[..])?;
+
+        event::emit(&TokenEvent::FaucetCreated {
+            mint: *mint_account.key,
+            amount_per_request,
+            cooldown_slots,
+        });
+
+        // 成功日志
+        msg!("Faucet created successfully.");
+
+        Ok(())
+    }
+
+    /// 从水龙头领取测试代币
+    ///
+    /// 首次领取时创建钱包的领取记录 PDA；之后距上次领取不足冷却时间则拒绝。
+    /// 代币由程序铸币权限 PDA 铸造到请求者的 ATA，仍受最大发行量限制
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 请求者账户 (签名，可写)
+    /// 2. 请求者关联令牌账户 (可写，不存在时自动创建)
+    /// 3. 铸币账户 (可写)
+    /// 4. 水龙头配置 PDA 账户 (只读)
+    /// 5. 领取记录 PDA 账户 (可写，不存在时自动创建)
+    /// 6. 程序铸币权限 PDA 账户 (只读)
+    /// 7. 代币配置 PDA 账户 (只读)
+    /// 8. 系统程序账户 (只读)
+    /// 9. 代币程序账户 (只读)
+    /// 10. 关联令牌账户程序 (只读)
+    pub fn request_tokens(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let requester = next_account_info(accounts_iter)?; // 请求者账户
+        let requester_token_account = next_account_info(accounts_iter)?; // 请求者关联令牌账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let faucet_account = next_account_info(accounts_iter)?; // 水龙头配置 PDA
+        let claim_account = next_account_info(accounts_iter)?; // 领取记录 PDA
+        let mint_authority = next_account_info(accounts_iter)?; // 程序铸币权限 PDA
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+
+        // ===== 账户验证 =====
+        check_signer(requester)?;
+        check_writable(requester)?;
+        check_writable(requester_token_account)?;
+        check_writable(mint_account)?;
+        check_writable(claim_account)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_associated_token_address(
+            requester_token_account,
+            requester.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+        check_program_mint_authority(program_id, mint_account)?;
+
+        let (faucet_pda, _) = find_faucet_address(program_id, mint_account.key);
+        let (claim_pda, claim_bump) =
+            find_faucet_claim_address(program_id, &faucet_pda, requester.key);
+        if faucet_account.key != &faucet_pda
+            || faucet_account.owner != program_id
+            || claim_account.key != &claim_pda
+        {
+            msg!(
+                "Invalid faucet accounts: expected {} / {}",
+                faucet_pda,
+                claim_pda
+            );
+            return Err(TokenError::InvalidFaucetAccount.into());
+        }
+        let faucet = FaucetConfig::try_from_slice(&faucet_account.data.borrow())?;
+
+        // 冷却时间检查（首次领取时创建领取记录）
+        let current_slot = Clock::get()?.slot;
+        if claim_account.data_is_empty() {
+            create_pda_account(
+                program_id,
+                requester,
+                claim_account,
+                system_program,
+                FaucetClaim::LEN,
+                &[
+                    FAUCET_CLAIM_SEED,
+                    faucet_account.key.as_ref(),
+                    requester.key.as_ref(),
+                    &[claim_bump],
+                ],
+            )?;
+        } else {
+            let claim = FaucetClaim::try_from_slice(&claim_account.data.borrow())?;
+            let next_slot = claim.next_claim_slot(faucet.cooldown_slots);
+            if current_slot < next_slot {
+                msg!(
+                    "Faucet cooldown active: next request at slot {} (current {})",
+                    next_slot,
+                    current_slot
+                );
+                return Err(TokenError::FaucetCooldownActive.into());
+            }
+        }
+        FaucetClaim::new(*requester.key, current_slot)
+            .serialize(&mut &mut claim_account.try_borrow_mut_data()?[..])?;
+
+        // 检查暂停状态与最大发行量
+        let config = load_config(program_id, config_account, mint_account.key)?;
+        check_can_mint(&config, mint_account, faucet.amount_per_request)?;
+
+        // 请求者关联令牌账户不存在时创建
+        create_associated_token_account_if_missing(
+            requester,
+            requester_token_account,
+            requester,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        // 由程序铸币权限 PDA 铸造
+        msg!("Minting {} faucet tokens...", faucet.amount_per_request);
+        mint_tokens(
+            program_id,
+            mint_account,
+            requester_token_account,
+            mint_authority,
+            token_program,
+            faucet.amount_per_request,
+            &[],
+        )?;
+
+        event::emit(&TokenEvent::FaucetRequested {
+            mint: *mint_account.key,
+            wallet: *requester.key,
+            amount: faucet.amount_per_request,
+            slot: current_slot,
+        });
+
+        // 成功日志
+        msg!("Faucet request completed successfully.");
+
+        Ok(())
+    }
+
+    /// 挂出两种代币之间的交换报价
+    ///
+    /// 做市方提供的代币锁入报价 PDA ["offer", maker, offered_mint, wanted_mint]
+    /// 的金库关联令牌账户，由 `TakeOffer` 原子性成交或 `CancelOffer` 撤销；
+    /// 提供的代币暂停期间拒绝
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `offered_amount`: 提供数量
+    /// - `wanted_mint`: 想要的代币
+    /// - `wanted_amount`: 想要的数量
+    ///
+    /// # 账户顺序要求
+    /// 1. 做市方账户 (签名，可写)
+    /// 2. 做市方提供代币的代币账户 (可写)
+    /// 3. 提供的代币铸币账户 (只读)
+    /// 4. 想要的代币铸币账户 (只读)
+    /// 5. 报价 PDA 账户 (可写)
+    /// 6. 金库关联令牌账户 (可写，报价 PDA 的 ATA)
+    /// 7. 系统程序账户 (只读)
+    /// 8. 提供的代币所属代币程序 (只读)
+    /// 9. 关联令牌账户程序 (只读)
+    /// 10. 提供的代币的代币配置 PDA 账户 (只读)
+    pub fn make_offer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        offered_amount: u64,
+        wanted_mint: Pubkey,
+        wanted_amount: u64,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let maker = next_account_info(accounts_iter)?; // 做市方账户
+        let maker_token_account = next_account_info(accounts_iter)?; // 做市方代币账户
+        let offered_mint_account = next_account_info(accounts_iter)?; // 提供的代币
+        let wanted_mint_account = next_account_info(accounts_iter)?; // 想要的代币
+        let offer_account = next_account_info(accounts_iter)?; // 报价 PDA
+        let vault = next_account_info(accounts_iter)?; // 金库关联令牌账户
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+        let offered_config_account = next_account_info(accounts_iter)?; // 提供的代币的代币配置 PDA
+
+        // ===== 账户验证 =====
+        check_signer(maker)?;
+        check_writable(maker)?;
+        check_writable(maker_token_account)?;
+        check_writable(offer_account)?;
+        check_writable(vault)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_not_paused(program_id, offered_config_account, offered_mint_account.key)?;
+
+        // 报价条件检查
+        if offered_amount == 0
+            || wanted_amount == 0
+            || wanted_mint_account.key != &wanted_mint
+            || offered_mint_account.key == wanted_mint_account.key
+        {
+            msg!("Invalid offer terms");
+            return Err(TokenError::InvalidOfferTerms.into());
+        }
+        unpack_mint(wanted_mint_account)?;
+
+        let (offer_pda, bump_seed) = find_offer_address(
+            program_id,
+            maker.key,
+            offered_mint_account.key,
+            wanted_mint_account.key,
+        );
+        if offer_account.key != &offer_pda {
+            msg!("Invalid offer account: expected {}", offer_pda);
+            return Err(TokenError::InvalidOfferAccount.into());
+        }
+        check_associated_token_address(
+            vault,
+            offer_account.key,
+            offered_mint_account.key,
+            token_program.key,
+        )?;
+
+        // 创建报价 PDA 并写入报价条件
+        create_pda_account(
+            program_id,
+            maker,
+            offer_account,
+            system_program,
+            Offer::LEN,
+            &[
+                OFFER_SEED,
+                maker.key.as_ref(),
+                offered_mint_account.key.as_ref(),
+                wanted_mint_account.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+        let offer = Offer::new(
+            *maker.key,
+            *offered_mint_account.key,
+            offered_amount,
+            wanted_mint,
+            wanted_amount,
+        );
+        offer.serialize(&mut &mut offer_account.try_borrow_mut_data()?[..])?;
+
+        // 金库关联令牌账户不存在时创建（所有者为报价 PDA）
+        create_associated_token_account_if_missing(
+            maker,
+            vault,
+            offer_account,
+            offered_mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        // 将提供的代币锁入金库
+        msg!(
+            "Offering {} of {} for {} of {}...",
+            offered_amount,
+            offered_mint_account.key,
+            wanted_amount,
+            wanted_mint
+        );
+        let decimals = unpack_mint(offered_mint_account)?.decimals;
+        transfer_tokens(
+            maker_token_account,
+            offered_mint_account,
+            vault,
+            maker,
+            token_program,
+            offered_amount,
+            decimals,
+            &[],
+        )?;
+
+        event::emit(&TokenEvent::OfferMade {
+            offer: *offer_account.key,
+            maker: *maker.key,
+            offered_mint: *offered_mint_account.key,
+            offered_amount,
+            wanted_mint,
+            wanted_amount,
+        });
+
+        // 成功日志
+        msg!("Offer created successfully.");
+
+        Ok(())
+    }
+
+    /// 接受交换报价
+    ///
+    /// 接受方将想要的代币支付到做市方的 ATA，同一笔交易内金库中的代币释放到
+    /// 接受方的 ATA；随后关闭金库与报价账户，租金退回做市方。任一代币暂停期间拒绝
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 接受方账户 (签名，可写)
+    /// 2. 做市方账户 (可写，接收退回的租金)
+    /// 3. 提供的代币铸币账户 (只读)
+    /// 4. 想要的代币铸币账户 (只读)
+    /// 5. 报价 PDA 账户 (可写)
+    /// 6. 金库关联令牌账户 (可写)
+    /// 7. 接受方支付想要代币的代币账户 (可写)
+    /// 8. 接受方提供代币的关联令牌账户 (可写，不存在时自动创建)
+    /// 9. 做市方想要代币的关联令牌账户 (可写，不存在时自动创建)
+    /// 10. 系统程序账户 (只读)
+    /// 11. 提供的代币所属代币程序 (只读)
+    /// 12. 想要的代币所属代币程序 (只读)
+    /// 13. 关联令牌账户程序 (只读)
+    /// 14. 提供的代币的代币配置 PDA 账户 (只读)
+    /// 15. 想要的代币的代币配置 PDA 账户 (只读)
+    pub fn take_offer(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let taker = next_account_info(accounts_iter)?; // 接受方账户
+        let maker = next_account_info(accounts_iter)?; // 做市方账户
+        let offered_mint_account = next_account_info(accounts_iter)?; // 提供的代币
+        let wanted_mint_account = next_account_info(accounts_iter)?; // 想要的代币
+        let offer_account = next_account_info(accounts_iter)?; // 报价 PDA
+        let vault = next_account_info(accounts_iter)?; // 金库关联令牌账户
+        let taker_payment_account = next_account_info(accounts_iter)?; // 接受方支付账户
+        let taker_receive_account = next_account_info(accounts_iter)?; // 接受方接收 ATA
+        let maker_receive_account = next_account_info(accounts_iter)?; // 做市方接收 ATA
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let offered_token_program = next_account_info(accounts_iter)?; // 提供代币的代币程序
+        let wanted_token_program = next_account_info(accounts_iter)?; // 想要代币的代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+        let offered_config_account = next_account_info(accounts_iter)?; // 提供代币的配置 PDA
+        let wanted_config_account = next_account_info(accounts_iter)?; // 想要代币的配置 PDA
+
+        // ===== 账户验证 =====
+        check_signer(taker)?;
+        check_writable(taker)?;
+        check_writable(maker)?;
+        check_writable(offer_account)?;
+        check_writable(vault)?;
+        check_writable(taker_payment_account)?;
+        check_writable(taker_receive_account)?;
+        check_writable(maker_receive_account)?;
+        check_system_program(system_program)?;
+        check_token_program(offered_token_program)?;
+        check_token_program(wanted_token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_not_paused(program_id, offered_config_account, offered_mint_account.key)?;
+        check_not_paused(program_id, wanted_config_account, wanted_mint_account.key)?;
+
+        let (offer_pda, bump_seed) = find_offer_address(
+            program_id,
+            maker.key,
+            offered_mint_account.key,
+            wanted_mint_account.key,
+        );
+        if offer_account.key != &offer_pda || offer_account.owner != program_id {
+            msg!("Invalid offer account: expected {}", offer_pda);
+            return Err(TokenError::InvalidOfferAccount.into());
+        }
+        let offer = Offer::try_from_slice(&offer_account.data.borrow())?;
+        check_associated_token_address(
+            vault,
+            offer_account.key,
+            offered_mint_account.key,
+            offered_token_program.key,
+        )?;
+        check_associated_token_address(
+            taker_receive_account,
+            taker.key,
+            offered_mint_account.key,
+            offered_token_program.key,
+        )?;
+        check_associated_token_address(
+            maker_receive_account,
+            maker.key,
+            wanted_mint_account.key,
+            wanted_token_program.key,
+        )?;
+
+        // 双方接收 ATA 不存在时创建（由接受方支付租金）
+        create_associated_token_account_if_missing(
+            taker,
+            taker_receive_account,
+            taker,
+            offered_mint_account,
+            system_program,
+            offered_token_program,
+            associated_token_program,
+        )?;
+        create_associated_token_account_if_missing(
+            taker,
+            maker_receive_account,
+            maker,
+            wanted_mint_account,
+            system_program,
+            wanted_token_program,
+            associated_token_program,
+        )?;
+
+        // 接受方支付想要的代币
+        msg!("Paying {} to maker...", offer.wanted_amount);
+        let wanted_decimals = unpack_mint(wanted_mint_account)?.decimals;
+        transfer_tokens(
+            taker_payment_account,
+            wanted_mint_account,
+            maker_receive_account,
+            taker,
+            wanted_token_program,
+            offer.wanted_amount,
+            wanted_decimals,
+            &[],
+        )?;
+
+        // 由报价 PDA 签名释放金库中的全部代币（带转账手续费的代币实际锁定量可能少于报价数量）
+        let offer_seeds: &[&[u8]] = &[
+            OFFER_SEED,
+            maker.key.as_ref(),
+            offered_mint_account.key.as_ref(),
+            wanted_mint_account.key.as_ref(),
+            &[bump_seed],
+        ];
+        let vault_amount = unpack_token_account(vault)?.amount;
+        msg!("Releasing {} from vault to taker...", vault_amount);
+        let offered_decimals = unpack_mint(offered_mint_account)?.decimals;
+        transfer_tokens(
+            vault,
+            offered_mint_account,
+            taker_receive_account,
+            offer_account,
+            offered_token_program,
+            vault_amount,
+            offered_decimals,
+            &[offer_seeds],
+        )?;
+
+        // 关闭金库与报价账户，租金退回做市方
+        close_vault_and_offer(
+            offer_account,
+            vault,
+            maker,
+            offered_token_program,
+            offer_seeds,
+        )?;
+
+        event::emit(&TokenEvent::OfferTaken {
+            offer: *offer_account.key,
+            taker: *taker.key,
+        });
+
+        // 成功日志
+        msg!("Offer taken successfully.");
+
+        Ok(())
+    }
+
+    /// 撤销交换报价
+    ///
+    /// 金库中的代币退回做市方指定的代币账户，关闭金库与报价账户并退回租金
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 做市方账户 (签名，可写)
+    /// 2. 做市方接收退回代币的代币账户 (可写)
+    /// 3. 提供的代币铸币账户 (只读)
+    /// 4. 想要的代币铸币账户 (只读)
+    /// 5. 报价 PDA 账户 (可写)
+    /// 6. 金库关联令牌账户 (可写)
+    /// 7. 提供的代币所属代币程序 (只读)
+    pub fn cancel_offer(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let maker = next_account_info(accounts_iter)?; // 做市方账户
+        let maker_token_account = next_account_info(accounts_iter)?; // 做市方接收代币账户
+        let offered_mint_account = next_account_info(accounts_iter)?; // 提供的代币
+        let wanted_mint_account = next_account_info(accounts_iter)?; // 想要的代币
+        let offer_account = next_account_info(accounts_iter)?; // 报价 PDA
+        let vault = next_account_info(accounts_iter)?; // 金库关联令牌账户
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+
+        // ===== 账户验证 =====
+        check_signer(maker)?;
+        check_writable(maker)?;
+        check_writable(maker_token_account)?;
+        check_writable(offer_account)?;
+        check_writable(vault)?;
+        check_token_program(token_program)?;
+
+        let (offer_pda, bump_seed) = find_offer_address(
+            program_id,
+            maker.key,
+            offered_mint_account.key,
+            wanted_mint_account.key,
+        );
+        if offer_account.key != &offer_pda || offer_account.owner != program_id {
+            msg!("Invalid offer account: expected {}", offer_pda);
+            return Err(TokenError::InvalidOfferAccount.into());
+        }
+        check_associated_token_address(
+            vault,
+            offer_account.key,
+            offered_mint_account.key,
+            token_program.key,
+        )?;
+
+        // 由报价 PDA 签名退回金库中的全部代币
+        let offer_seeds: &[&[u8]] = &[
+            OFFER_SEED,
+            maker.key.as_ref(),
+            offered_mint_account.key.as_ref(),
+            wanted_mint_account.key.as_ref(),
+            &[bump_seed],
+        ];
+        let vault_amount = unpack_token_account(vault)?.amount;
+        msg!("Returning {} from vault to maker...", vault_amount);
+        let decimals = unpack_mint(offered_mint_account)?.decimals;
+        transfer_tokens(
+            vault,
+            offered_mint_account,
+            maker_token_account,
+            offer_account,
+            token_program,
+            vault_amount,
+            decimals,
+            &[offer_seeds],
+        )?;
+
+        // 关闭金库与报价账户，租金退回做市方
+        close_vault_and_offer(offer_account, vault, maker, token_program, offer_seeds)?;
+
+        event::emit(&TokenEvent::OfferCancelled {
+            offer: *offer_account.key,
+        });
+
+        // 成功日志
+        msg!("Offer cancelled successfully.");
+
+        Ok(())
+    }
+
+    /// 暂停或恢复代币（事故响应）
+    ///
+    /// 暂停期间拒绝铸造（Mint / BatchMint / Claim / RequestTokens / Buy / ClaimRewards），
+    /// 以及经由本程序的转账、托管和销毁（Transfer / TransferFrom / Burn / CreateVesting /
+    /// ClaimVested / MakeOffer / TakeOffer / Stake）；撤销报价与解除质押不受影响，
+    /// 用户总能取回自己锁定的代币
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `paused`: true 为暂停，false 为恢复
+    ///
+    /// # 账户顺序要求
+    /// 1. 管理员账户 (签名)
+    /// 2. 铸币账户 (只读)
+    /// 3. 代币配置 PDA 账户 (可写)
+    pub fn set_paused(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        paused: bool,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let admin = next_account_info(accounts_iter)?; // 管理员账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+
+        // ===== 账户验证 =====
+        check_writable(config_account)?;
+
+        let mut config = load_config_as_admin(program_id, config_account, mint_account.key, admin)?;
+
+        // 更新暂停标志
+        msg!(
+            "{} token {}...",
+            if paused { "Pausing" } else { "Unpausing" },
+            mint_account.key
+        );
+        config.paused = paused;
+        config.serialize(&mut &mut config_account.try_borrow_mut_data()?[..])?;
+
+        event::emit(&TokenEvent::PauseUpdated {
+            mint: *mint_account.key,
+            paused,
+        });
+
+        // 成功日志
+        msg!("Token pause state updated successfully.");
+
+        Ok(())
+    }
+
+    /// 创建公开发售（以固定 SOL 价格出售代币）
+    ///
+    /// 铸币权限必须是程序 PDA，且签名者必须是代币配置中的管理员；
+    /// 价格不能为 0，国库不能是默认地址（系统程序），否则购买者可以免费铸造或 SOL 无法取回
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `price_lamports`: 每个完整代币的价格（lamports）
+    /// - `treasury`: 收款的国库账户
+    /// - `wallet_cap`: 单个钱包的购买上限（最小单位），None 表示不限
+    ///
+    /// # 账户顺序要求
+    /// 1. 管理员账户 (签名，可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 代币配置 PDA 账户 (只读)
+    /// 4. 发售配置 PDA 账户 (可写)
+    /// 5. 系统程序账户 (只读)
+    pub fn create_sale(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        price_lamports: u64,
+        treasury: Pubkey,
+        wallet_cap: Option<u64>,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let admin = next_account_info(accounts_iter)?; // 管理员账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let sale_account = next_account_info(accounts_iter)?; // 发售配置 PDA
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+
+        // ===== 账户验证 =====
+        check_writable(admin)?;
+        check_writable(sale_account)?;
+        check_system_program(system_program)?;
+        check_program_mint_authority(program_id, mint_account)?;
+        check_mint_authority_signer(program_id, mint_account, config_account, admin, &[])?;
+
+        let (sale_pda, bump_seed) = find_sale_address(program_id, mint_account.key);
+        if sale_account.key != &sale_pda {
+            msg!("Invalid sale account: expected {}", sale_pda);
+            return Err(TokenError::InvalidSaleAccount.into());
+        }
+
+        // 发售条件检查
+        if price_lamports == 0 || treasury == Pubkey::default() {
+            msg!(
+                "Invalid sale terms: price {} lamports, treasury {}",
+                price_lamports,
+                treasury
+            );
+            return Err(TokenError::InvalidSaleTerms.into());
+        }
+
+        // 创建发售配置 PDA 并写入价格与国库账户
+        msg!(
+            "Creating sale: {} lamports per token, treasury {}...",
+            price_lamports,
+            treasury
+        );
+        create_pda_account(
+            program_id,
+            admin,
+            sale_account,
+            system_program,
+            SaleConfig::LEN,
+            &[SALE_SEED, mint_account.key.as_ref(), &[bump_seed]],
+        )?;
+        let sale = SaleConfig::new(*mint_account.key, price_lamports, treasury, wallet_cap);
+        sale.serialize(&mut &mut sale_account.try_borrow_mut_data()?[..])?;
+
+        event::emit(&TokenEvent::SaleCreated {
+            mint: *mint_account.key,
+            price_lamports,
+            treasury,
+            wallet_cap,
+        });
+
+        // 成功日志
+        msg!("Sale created successfully.");
+
+        Ok(())
+    }
+
+    /// 以固定 SOL 价格购买代币
+    ///
+    /// 同一指令内：通过系统程序转账将 SOL 从买家转入国库，
+    /// 再由程序铸币权限 PDA 铸造到买家的 ATA；受单钱包上限、暂停状态与最大发行量限制
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `amount`: 购买数量（最小单位）
+    ///
+    /// # 账户顺序要求
+    /// 1. 买家账户 (签名，可写)
+    /// 2. 买家关联令牌账户 (可写，不存在时自动创建)
+    /// 3. 铸币账户 (可写)
+    /// 4. 发售配置 PDA 账户 (只读)
+    /// 5. 购买记录 PDA 账户 (可写，不存在时自动创建)
+    /// 6. 国库账户 (可写)
+    /// 7. 程序铸币权限 PDA 账户 (只读)
+    /// 8. 代币配置 PDA 账户 (只读)
+    /// 9. 系统程序账户 (只读)
+    /// 10. 代币程序账户 (只读)
+    /// 11. 关联令牌账户程序 (只读)
+    pub fn buy(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let buyer = next_account_info(accounts_iter)?; // 买家账户
+        let buyer_token_account = next_account_info(accounts_iter)?; // 买家关联令牌账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let sale_account = next_account_info(accounts_iter)?; // 发售配置 PDA
+        let purchase_account = next_account_info(accounts_iter)?; // 购买记录 PDA
+        let treasury = next_account_info(accounts_iter)?; // 国库账户
+        let mint_authority = next_account_info(accounts_iter)?; // 程序铸币权限 PDA
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+
+        // ===== 账户验证 =====
+        check_signer(buyer)?;
+        check_writable(buyer)?;
+        check_writable(buyer_token_account)?;
+        check_writable(mint_account)?;
+        check_writable(purchase_account)?;
+        check_writable(treasury)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_associated_token_address(
+            buyer_token_account,
+            buyer.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+        check_program_mint_authority(program_id, mint_account)?;
+
+        let (sale_pda, _) = find_sale_address(program_id, mint_account.key);
+        let (purchase_pda, purchase_bump) =
+            find_sale_purchase_address(program_id, &sale_pda, buyer.key);
+        if sale_account.key != &sale_pda
+            || sale_account.owner != program_id
+            || purchase_account.key != &purchase_pda
+        {
+            msg!(
+                "Invalid sale accounts: expected {} / {}",
+                sale_pda,
+                purchase_pda
+            );
+            return Err(TokenError::InvalidSaleAccount.into());
+        }
+        // wallet_cap 为 None 时序列化结果短于 SaleConfig::LEN，只读取前缀
+        let sale = SaleConfig::deserialize(&mut &sale_account.data.borrow()[..])?;
+        if treasury.key != &sale.treasury {
+            msg!("Invalid treasury: expected {}", sale.treasury);
+            return Err(TokenError::InvalidTreasury.into());
+        }
+
+        // 检查暂停状态与最大发行量
+        let config = load_config(program_id, config_account, mint_account.key)?;
+        check_can_mint(&config, mint_account, amount)?;
+
+        // 检查单钱包购买上限（首次购买时创建购买记录）
+        let mut purchase = if purchase_account.data_is_empty() {
+            create_pda_account(
+                program_id,
+                buyer,
+                purchase_account,
+                system_program,
+                SalePurchase::LEN,
+                &[
+                    SALE_PURCHASE_SEED,
+                    sale_account.key.as_ref(),
+                    buyer.key.as_ref(),
+                    &[purchase_bump],
+                ],
+            )?;
+            SalePurchase::new(*buyer.key)
+        } else {
+            SalePurchase::try_from_slice(&purchase_account.data.borrow())?
+        };
+        if !sale.within_cap(purchase.purchased, amount) {
+            msg!(
+                "Buying {} would exceed wallet cap {:?} (already purchased {})",
+                amount,
+                sale.wallet_cap,
+                purchase.purchased
+            );
+            return Err(TokenError::WalletCapExceeded.into());
+        }
+
+        // 计算价格并将 SOL 转入国库
+        let lamports = sale
+            .cost(amount, config.decimals)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        msg!("Paying {} lamports for {} tokens...", lamports, amount);
+
+        // 关键API: system_instruction::transfer
+        // 功能: 创建 SOL 转账指令
+        // 参数:
+        //   buyer.key - 付款账户（买家）
+        //   treasury.key - 收款账户（国库）
+        //   lamports - 转账金额
+        invoke(
+            &system_instruction::transfer(buyer.key, treasury.key, lamports),
+            &[buyer.clone(), treasury.clone(), system_program.clone()],
+        )?;
+
+        // 买家关联令牌账户不存在时创建
+        create_associated_token_account_if_missing(
+            buyer,
+            buyer_token_account,
+            buyer,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        // 由程序铸币权限 PDA 铸造到买家
+        mint_tokens(
+            program_id,
+            mint_account,
+            buyer_token_account,
+            mint_authority,
+            token_program,
+            amount,
+            &[],
+        )?;
+
+        // 更新购买记录
+        purchase.purchased = purchase
+            .purchased
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        purchase.serialize(&mut &mut purchase_account.try_borrow_mut_data()?[..])?;
+
+        event::emit(&TokenEvent::Purchased {
+            mint: *mint_account.key,
+            buyer: *buyer.key,
+            amount,
+            lamports,
+        });
+
+        // 成功日志
+        msg!("Tokens purchased successfully.");
+
+        Ok(())
+    }
+
+    /// 将钱包加入白名单并设置可铸造额度
+    ///
+    /// 白名单 PDA ["allowlist", mint, wallet] 不存在时创建，已存在时覆盖剩余额度
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `wallet`: 白名单钱包
+    /// - `allowance`: 可铸造额度（最小单位）
+    ///
+    /// # 账户顺序要求
+    /// 1. 管理员账户 (签名，可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 代币配置 PDA 账户 (只读)
+    /// 4. 白名单 PDA 账户 (可写)
+    /// 5. 系统程序账户 (只读)
+    pub fn add_to_allowlist(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        wallet: Pubkey,
+        allowance: u64,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let admin = next_account_info(accounts_iter)?; // 管理员账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let allowlist_account = next_account_info(accounts_iter)?; // 白名单 PDA
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+
+        // ===== 账户验证 =====
+        check_writable(admin)?;
+        check_writable(allowlist_account)?;
+        check_system_program(system_program)?;
+        load_config_as_admin(program_id, config_account, mint_account.key, admin)?;
+
+        let (allowlist_pda, bump_seed) =
+            find_allowlist_address(program_id, mint_account.key, &wallet);
+        if allowlist_account.key != &allowlist_pda {
+            msg!("Invalid allowlist account: expected {}", allowlist_pda);
+            return Err(TokenError::InvalidAllowlistAccount.into());
+        }
+
+        // 白名单 PDA 不存在时创建
+        if allowlist_account.data_is_empty() {
+            create_pda_account(
+                program_id,
+                admin,
+                allowlist_account,
+                system_program,
+                AllowlistEntry::LEN,
+                &[
+                    ALLOWLIST_SEED,
+                    mint_account.key.as_ref(),
+                    wallet.as_ref(),
+                    &[bump_seed],
+                ],
+            )?;
+        }
+
+        // 写入（覆盖）剩余额度
+        msg!("Allowlisting {} with allowance {}...", wallet, allowance);
+        let entry = AllowlistEntry::new(*mint_account.key, wallet, allowance);
+        entry.serialize(&mut &mut allowlist_account.try_borrow_mut_data()?[..])?;
+
+        event::emit(&TokenEvent::AllowlistUpdated {
+            mint: *mint_account.key,
+            wallet,
+            allowance,
+        });
+
+        // 成功日志
+        msg!("Allowlist updated successfully.");
+
+        Ok(())
+    }
+
+    /// 将钱包移出白名单
+    ///
+    /// 关闭白名单 PDA，租金退回管理员
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 管理员账户 (签名，可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 代币配置 PDA 账户 (只读)
+    /// 4. 白名单 PDA 账户 (可写)
+    pub fn remove_from_allowlist(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let admin = next_account_info(accounts_iter)?; // 管理员账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let allowlist_account = next_account_info(accounts_iter)?; // 白名单 PDA
+
+        // ===== 账户验证 =====
+        check_writable(admin)?;
+        check_writable(allowlist_account)?;
+        load_config_as_admin(program_id, config_account, mint_account.key, admin)?;
+
+        if allowlist_account.owner != program_id {
+            msg!("Allowlist account {} does not exist", allowlist_account.key);
+            return Err(TokenError::InvalidAllowlistAccount.into());
+        }
+        let entry = AllowlistEntry::try_from_slice(&allowlist_account.data.borrow())?;
+        let (allowlist_pda, _) =
+            find_allowlist_address(program_id, mint_account.key, &entry.wallet);
+        if allowlist_account.key != &allowlist_pda {
+            msg!("Invalid allowlist account: expected {}", allowlist_pda);
+            return Err(TokenError::InvalidAllowlistAccount.into());
+        }
+
+        // 关闭白名单 PDA，租金退回管理员
+        msg!("Removing {} from allowlist...", entry.wallet);
+        close_program_account(allowlist_account, admin)?;
+
+        event::emit(&TokenEvent::AllowlistRemoved {
+            mint: *mint_account.key,
+            wallet: entry.wallet,
+        });
+
+        // 成功日志
+        msg!("Wallet removed from allowlist successfully.");
+
+        Ok(())
+    }
+
+    /// 创建质押池
+    ///
+    /// 质押的代币存放在质押池 PDA ["stake_pool", mint] 的金库关联令牌账户中；
+    /// 奖励由程序铸币权限 PDA 铸造，因此铸币权限必须是程序 PDA，且签名者必须是管理员
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `reward_rate`: 每秒释放的奖励总量（最小单位）
+    ///
+    /// # 账户顺序要求
+    /// 1. 管理员账户 (签名，可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 代币配置 PDA 账户 (只读)
+    /// 4. 质押池 PDA 账户 (可写)
+    /// 5. 金库关联令牌账户 (可写，质押池 PDA 的 ATA)
+    /// 6. 系统程序账户 (只读)
+    /// 7. 代币程序账户 (只读)
+    /// 8. 关联令牌账户程序 (只读)
+    pub fn init_pool(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        reward_rate: u64,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let admin = next_account_info(accounts_iter)?; // 管理员账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let pool_account = next_account_info(accounts_iter)?; // 质押池 PDA
+        let vault = next_account_info(accounts_iter)?; // 金库关联令牌账户
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+
+        // ===== 账户验证 =====
+        check_writable(admin)?;
+        check_writable(pool_account)?;
+        check_writable(vault)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_program_mint_authority(program_id, mint_account)?;
+        check_mint_authority_signer(program_id, mint_account, config_account, admin, &[])?;
+
+        let (pool_pda, bump_seed) = find_stake_pool_address(program_id, mint_account.key);
+        if pool_account.key != &pool_pda {
+            msg!("Invalid stake pool account: expected {}", pool_pda);
+            return Err(TokenError::InvalidStakeAccount.into());
+        }
+        check_associated_token_address(
+            vault,
+            pool_account.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+
+        // 创建质押池 PDA
+        msg!(
+            "Creating stake pool, reward rate {} per second...",
+            reward_rate
+        );
+        create_pda_account(
+            program_id,
+            admin,
+            pool_account,
+            system_program,
+            StakePool::LEN,
+            &[STAKE_POOL_SEED, mint_account.key.as_ref(), &[bump_seed]],
+        )?;
+        let pool = StakePool::new(*mint_account.key, reward_rate, Clock::get()?.unix_timestamp);
+        pool.serialize(&mut &mut pool_account.try_borrow_mut_data()?[..])?;
+
+        // 创建金库关联令牌账户（所有者为质押池 PDA）
+        create_associated_token_account_if_missing(
+            admin,
+            vault,
+            pool_account,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        event::emit(&TokenEvent::PoolCreated {
+            mint: *mint_account.key,
+            reward_rate,
+        });
+
+        // 成功日志
+        msg!("Stake pool created successfully.");
+
+        Ok(())
+    }
+
+    /// 质押代币
+    ///
+    /// 先将累加器推进到当前时间并结算用户已产生的奖励，再把代币转入金库。
+    /// 质押量按金库实际到账数量记录（兼容带转账手续费的代币）；代币暂停期间拒绝
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `amount`: 质押数量
+    ///
+    /// # 账户顺序要求
+    /// 1. 质押者账户 (签名，可写)
+    /// 2. 质押者代币账户 (可写)
+    /// 3. 铸币账户 (只读)
+    /// 4. 质押池 PDA 账户 (可写)
+    /// 5. 用户质押 PDA 账户 (可写，不存在时自动创建)
+    /// 6. 金库关联令牌账户 (可写)
+    /// 7. 系统程序账户 (只读)
+    /// 8. 代币程序账户 (只读)
+    /// 9. 代币配置 PDA 账户 (只读)
+    pub fn stake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let owner = next_account_info(accounts_iter)?; // 质押者账户
+        let owner_token_account = next_account_info(accounts_iter)?; // 质押者代币账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let pool_account = next_account_info(accounts_iter)?; // 质押池 PDA
+        let user_stake_account = next_account_info(accounts_iter)?; // 用户质押 PDA
+        let vault = next_account_info(accounts_iter)?; // 金库关联令牌账户
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+
+        // ===== 账户验证 =====
+        check_signer(owner)?;
+        check_writable(owner)?;
+        check_writable(owner_token_account)?;
+        check_writable(pool_account)?;
+        check_writable(user_stake_account)?;
+        check_writable(vault)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_not_paused(program_id, config_account, mint_account.key)?;
+
+        let mut pool = load_stake_pool(program_id, pool_account, mint_account.key)?;
+        check_associated_token_address(
+            vault,
+            pool_account.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+        let (user_stake_pda, user_stake_bump) =
+            find_user_stake_address(program_id, pool_account.key, owner.key);
+        if user_stake_account.key != &user_stake_pda {
+            msg!("Invalid user stake account: expected {}", user_stake_pda);
+            return Err(TokenError::InvalidStakeAccount.into());
+        }
+
+        // 推进累加器（首次质押时创建用户质押记录）
+        pool.update(Clock::get()?.unix_timestamp)?;
+        let mut user_stake = if user_stake_account.data_is_empty() {
+            create_pda_account(
+                program_id,
+                owner,
+                user_stake_account,
+                system_program,
+                UserStake::LEN,
+                &[
+                    USER_STAKE_SEED,
+                    pool_account.key.as_ref(),
+                    owner.key.as_ref(),
+                    &[user_stake_bump],
+                ],
+            )?;
+            UserStake::new(*owner.key, pool.acc_reward_per_token)
+        } else {
+            UserStake::try_from_slice(&user_stake_account.data.borrow())?
+        };
+        user_stake.settle(&pool)?;
+
+        // 转入金库，按实际到账数量记录质押量
+        msg!("Staking {} tokens...", amount);
+        let vault_before = unpack_token_account(vault)?.amount;
+        let decimals = unpack_mint(mint_account)?.decimals;
+        transfer_tokens(
+            owner_token_account,
+            mint_account,
+            vault,
+            owner,
+            token_program,
+            amount,
+            decimals,
+            &[],
+        )?;
+        let received = unpack_token_account(vault)?.amount - vault_before;
+
+        user_stake.amount = user_stake
+            .amount
+            .checked_add(received)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        pool.total_staked = pool
+            .total_staked
+            .checked_add(received)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        user_stake.serialize(&mut &mut user_stake_account.try_borrow_mut_data()?[..])?;
+        pool.serialize(&mut &mut pool_account.try_borrow_mut_data()?[..])?;
+
+        event::emit(&TokenEvent::Staked {
+            mint: *mint_account.key,
+            owner: *owner.key,
+            amount: received,
+        });
+
+        // 成功日志
+        msg!("Staked successfully, total staked {}.", user_stake.amount);
+
+        Ok(())
+    }
+
+    /// 解除质押
+    ///
+    /// 先结算奖励，再由质押池 PDA 签名从金库转出；已结算的奖励保留，可随后领取。
+    /// 奖励结算溢出时放弃本次未结算的奖励，保证本金总能取回（代币暂停期间同样允许）
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `amount`: 解除质押数量
+    ///
+    /// # 账户顺序要求
+    /// 1. 质押者账户 (签名)
+    /// 2. 接收代币的代币账户 (可写)
+    /// 3. 铸币账户 (只读)
+    /// 4. 质押池 PDA 账户 (可写)
+    /// 5. 用户质押 PDA 账户 (可写)
+    /// 6. 金库关联令牌账户 (可写)
+    /// 7. 代币程序账户 (只读)
+    pub fn unstake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let owner = next_account_info(accounts_iter)?; // 质押者账户
+        let owner_token_account = next_account_info(accounts_iter)?; // 接收代币账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let pool_account = next_account_info(accounts_iter)?; // 质押池 PDA
+        let user_stake_account = next_account_info(accounts_iter)?; // 用户质押 PDA
+        let vault = next_account_info(accounts_iter)?; // 金库关联令牌账户
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+
+        // ===== 账户验证 =====
+        check_signer(owner)?;
+        check_writable(owner_token_account)?;
+        check_writable(pool_account)?;
+        check_writable(user_stake_account)?;
+        check_writable(vault)?;
+        check_token_program(token_program)?;
+
+        let mut pool = load_stake_pool(program_id, pool_account, mint_account.key)?;
+        check_associated_token_address(
+            vault,
+            pool_account.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+        let mut user_stake =
+            load_user_stake(program_id, user_stake_account, pool_account.key, owner.key)?;
+        if amount > user_stake.amount {
+            msg!(
+                "Cannot unstake {}: only {} staked",
+                amount,
+                user_stake.amount
+            );
+            return Err(TokenError::InsufficientStake.into());
+        }
+
+        // 推进累加器并结算奖励；失败时只放弃未结算的奖励，不阻止取回本金
+        let now = Clock::get()?.unix_timestamp;
+        if let Err(error) = pool.update(now).and_then(|()| user_stake.settle(&pool)) {
+            msg!(
+                "Reward settlement failed ({}), forfeiting unsettled rewards",
+                error
+            );
+            user_stake.reward_per_token_paid = pool.acc_reward_per_token;
+        }
+        user_stake.amount = user_stake
+            .amount
+            .checked_sub(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        pool.total_staked = pool
+            .total_staked
+            .checked_sub(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        user_stake.serialize(&mut &mut user_stake_account.try_borrow_mut_data()?[..])?;
+        pool.serialize(&mut &mut pool_account.try_borrow_mut_data()?[..])?;
+
+        // 由质押池 PDA 签名从金库转出
+        msg!("Unstaking {} tokens...", amount);
+        let (_, bump_seed) = find_stake_pool_address(program_id, mint_account.key);
+        let decimals = unpack_mint(mint_account)?.decimals;
+        transfer_tokens(
+            vault,
+            mint_account,
+            owner_token_account,
+            pool_account,
+            token_program,
+            amount,
+            decimals,
+            &[&[STAKE_POOL_SEED, mint_account.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        event::emit(&TokenEvent::Unstaked {
+            mint: *mint_account.key,
+            owner: *owner.key,
+            amount,
+        });
+
+        // 成功日志
+        msg!(
+            "Unstaked successfully, remaining stake {}.",
+            user_stake.amount
+        );
+
+        Ok(())
+    }
+
+    /// 领取质押奖励
+    ///
+    /// 推进累加器并结算后，由程序铸币权限 PDA 将待领取奖励铸造到质押者的 ATA；
+    /// 受暂停状态与最大发行量限制
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 质押者账户 (签名，可写)
+    /// 2. 质押者关联令牌账户 (可写，不存在时自动创建)
+    /// 3. 铸币账户 (可写)
+    /// 4. 质押池 PDA 账户 (可写)
+    /// 5. 用户质押 PDA 账户 (可写)
+    /// 6. 程序铸币权限 PDA 账户 (只读)
+    /// 7. 代币配置 PDA 账户 (只读)
+    /// 8. 系统程序账户 (只读)
+    /// 9. 代币程序账户 (只读)
+    /// 10. 关联令牌账户程序 (只读)
+    pub fn claim_rewards(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let owner = next_account_info(accounts_iter)?; // 质押者账户
+        let owner_token_account = next_account_info(accounts_iter)?; // 质押者关联令牌账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let pool_account = next_account_info(accounts_iter)?; // 质押池 PDA
+        let user_stake_account = next_account_info(accounts_iter)?; // 用户质押 PDA
+        let mint_authority = next_account_info(accounts_iter)?; // 程序铸币权限 PDA
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+
+        // ===== 账户验证 =====
+        check_signer(owner)?;
+        check_writable(owner)?;
+        check_writable(owner_token_account)?;
+        check_writable(mint_account)?;
+        check_writable(pool_account)?;
+        check_writable(user_stake_account)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_associated_token_address(
+            owner_token_account,
+            owner.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+        check_program_mint_authority(program_id, mint_account)?;
+
+        let mut pool = load_stake_pool(program_id, pool_account, mint_account.key)?;
+        let mut user_stake =
+            load_user_stake(program_id, user_stake_account, pool_account.key, owner.key)?;
+
+        // 推进累加器并结算奖励
+        pool.update(Clock::get()?.unix_timestamp)?;
+        user_stake.settle(&pool)?;
+        let rewards = user_stake.pending_rewards;
+        if rewards == 0 {
+            msg!("No rewards to claim");
+            return Err(TokenError::NothingToClaim.into());
+        }
+
+        // 检查暂停状态与最大发行量
+        let config = load_config(program_id, config_account, mint_account.key)?;
+        check_can_mint(&config, mint_account, rewards)?;
+
+        user_stake.pending_rewards = 0;
+        user_stake.serialize(&mut &mut user_stake_account.try_borrow_mut_data()?[..])?;
+        pool.serialize(&mut &mut pool_account.try_borrow_mut_data()?[..])?;
+
+        // 质押者关联令牌账户不存在时创建
+        create_associated_token_account_if_missing(
+            owner,
+            owner_token_account,
+            owner,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        // 由程序铸币权限 PDA 铸造奖励
+        msg!("Minting {} reward tokens...", rewards);
+        mint_tokens(
+            program_id,
+            mint_account,
+            owner_token_account,
+            mint_authority,
+            token_program,
+            rewards,
+            &[],
+        )?;
+
+        event::emit(&TokenEvent::RewardsClaimed {
+            mint: *mint_account.key,
+            owner: *owner.key,
+            amount: rewards,
+        });
+
+        // 成功日志
+        msg!("Rewards claimed successfully.");
+
+        Ok(())
+    }
+
+    /// 变更或永久撤销铸币 / 冻结权限
+    ///
+    /// 当前权限必须签名；当前权限为程序铸币权限 PDA 时，由代币配置中的管理员签名，
+    /// 程序使用 `invoke_signed` 代为签署 `set_authority`；当前权限为 spl-token 多签账户时，
+    /// 多签签名者随 CPI 一并转发，由代币程序校验 M-of-N。
+    /// `new_authority` 为 `None` 时权限被永久撤销（铸币权限撤销后发行量固定）
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `authority_type`: 权限类型（铸币或冻结）
+    /// - `new_authority`: 新权限（`None` 表示撤销）
+    ///
+    /// # 账户顺序要求
+    /// 1. 铸币账户 (可写)
+    /// 2. 当前权限账户 (签名，或程序铸币权限 PDA / 多签账户)
+    /// 3. 代币程序账户 (只读)
+    ///
+    /// 当前权限为程序 PDA 时其后传入代币配置 PDA 账户 (只读) 与管理员账户 (签名)；
+    /// 当前权限为多签账户时其后依次传入参与签名的多签签名者账户 (签名)
+    pub fn set_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        authority_type: AuthorityType,
+        new_authority: Option<Pubkey>,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let current_authority = next_account_info(accounts_iter)?; // 当前权限账户
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let remaining_accounts = accounts_iter.as_slice(); // 剩余账户：配置与管理员，或多签签名者
+
+        // ===== 账户验证 =====
+        check_writable(mint_account)?;
+        check_token_program(token_program)?;
+
+        // 读取铸币账户中的当前权限，并确认与传入账户一致
+        let mint = unpack_mint(mint_account)?;
+        let (expected, spl_authority_type, error) = match authority_type {
+            AuthorityType::MintTokens => (
+                mint.mint_authority,
+                SplAuthorityType::MintTokens,
+                TokenError::InvalidMintAuthority,
+            ),
+            AuthorityType::FreezeAccount => (
+                mint.freeze_authority,
+                SplAuthorityType::FreezeAccount,
+                TokenError::InvalidFreezeAuthority,
+            ),
+        };
+        if expected != COption::Some(*current_authority.key) {
+            msg!(
+                "{} is not the current {:?} authority of mint {}",
+                current_authority.key,
+                authority_type,
+                mint_account.key
+            );
+            return Err(error.into());
+        }
+
+        msg!(
+            "Setting {:?} authority of mint {} to {:?}...",
+            authority_type,
+            mint_account.key,
+            new_authority
+        );
+
+        // 关键API: set_authority
+        // 功能: 创建设置权限指令
+        // 参数:
+        //   token_program.key - 代币程序
+        //   mint_account.key - 铸币账户
+        //   new_authority.as_ref() - 新权限（None 表示撤销）
+        //   spl_authority_type - 权限类型
+        //   current_authority.key - 当前权限
+        //   &signer_pubkeys - 多签签名者（非多签时为空）
+        let (mint_authority_pda, bump_seed) =
+            find_mint_authority_address(program_id, mint_account.key);
+        let multisig_signers = if current_authority.key != &mint_authority_pda
+            && unpack_multisig(current_authority).is_some()
+        {
+            remaining_accounts
+        } else {
+            &[]
+        };
+        let signer_pubkeys: Vec<&Pubkey> =
+            multisig_signers.iter().map(|signer| signer.key).collect();
+        let ix = set_authority(
+            token_program.key,
+            mint_account.key,
+            new_authority.as_ref(),
+            spl_authority_type,
+            current_authority.key,
+            &signer_pubkeys,
+        )?;
+        let mut account_infos = vec![
+            mint_account.clone(),
+            current_authority.clone(),
+            token_program.clone(),
+        ];
+        account_infos.extend(multisig_signers.iter().cloned());
+
+        if current_authority.key == &mint_authority_pda {
+            // 当前权限为程序 PDA：管理员签名，程序使用种子签名
+            let [config_account, admin, ..] = remaining_accounts else {
+                msg!("Config and admin accounts are required for program mint authority");
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            load_config_as_admin(program_id, config_account, mint_account.key, admin)?;
+            invoke_signed(
+                &ix,
+                &account_infos,
+                &[&[
+                    MINT_AUTHORITY_SEED,       // 固定种子
+                    mint_account.key.as_ref(), // 铸币账户
+                    &[bump_seed],              // bump seed
+                ]],
+            )?;
+        } else if !multisig_signers.is_empty() {
+            // 多签权限：由代币程序校验签名者集合满足 M-of-N
+            msg!(
+                "Setting authority with {} multisig signers...",
+                multisig_signers.len()
+            );
+            invoke(&ix, &account_infos)?;
+        } else {
+            check_signer(current_authority)?; // 当前权限必须签名
+            invoke(&ix, &account_infos)?;
+        }
+
+        event::emit(&TokenEvent::AuthorityChanged {
+            mint: *mint_account.key,
+            authority_type,
+            new_authority,
+        });
+
+        // 成功日志
+        msg!("Authority updated successfully.");
+
+        Ok(())
+    }
+
+    /// 授权委托人从源代币账户转出不超过 `amount` 的代币
+    ///
+    /// 使用 `approve_checked`，小数位数从铸币账户读取；再次授权会覆盖之前的委托
+    ///
+    /// # 参数
+    /// - `accounts`: 传入的账户列表
+    /// - `delegate`: 委托人
+    /// - `amount`: 委托额度
+    ///
+    /// # 账户顺序要求
+    /// 1. 源代币账户 (可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 委托人账户 (只读，必须等于 `delegate`)
+    /// 4. 源账户所有者 (签名)
+    /// 5. 代币程序账户 (只读)
+    pub fn approve(accounts: &[AccountInfo], delegate: Pubkey, amount: u64) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let source_account = next_account_info(accounts_iter)?; // 源代币账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let delegate_account = next_account_info(accounts_iter)?; // 委托人账户
+        let owner = next_account_info(accounts_iter)?; // 源账户所有者
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+
+        // ===== 账户验证 =====
+        check_writable(source_account)?;
+        check_signer(owner)?; // 所有者必须签名
+        check_token_program(token_program)?;
+        if delegate_account.key != &delegate {
+            msg!(
+                "Delegate account {} does not match {}",
+                delegate_account.key,
+                delegate
+            );
+            return Err(TokenError::InvalidDelegate.into());
+        }
+
+        let decimals = unpack_mint(mint_account)?.decimals;
+        msg!(
+            "Approving {} to spend {} tokens from {}...",
+            delegate,
+            amount,
+            source_account.key
+        );
+
+        // 关键API: approve_checked
+        // 功能: 创建带小数位数校验的委托授权指令
+        // 参数:
+        //   token_program.key - 代币程序
+        //   source_account.key - 源代币账户
+        //   mint_account.key - 铸币账户（用于校验小数位数）
+        //   delegate_account.key - 委托人
+        //   owner.key - 源账户所有者
+        //   &[] - 多签签名者（无）
+        //   amount - 委托额度
+        //   decimals - 小数位数
+        invoke(
+            &approve_checked(
+                token_program.key,
+                source_account.key,
+                mint_account.key,
+                delegate_account.key,
+                owner.key,
+                &[],
+                amount,
+                decimals,
+            )?,
+            &[
+                source_account.clone(),
+                mint_account.clone(),
+                delegate_account.clone(),
+                owner.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        event::emit(&TokenEvent::Approved {
+            mint: *mint_account.key,
+            account: *source_account.key,
+            delegate,
+            amount,
+        });
+
+        // 成功日志
+        msg!("Delegate approved successfully.");
+
+        Ok(())
+    }
+
+    /// 撤销源代币账户的委托
+    ///
+    /// # 参数
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 源代币账户 (可写)
+    /// 2. 源账户所有者 (签名)
+    /// 3. 代币程序账户 (只读)
+    pub fn revoke(accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let source_account = next_account_info(accounts_iter)?; // 源代币账户
+        let owner = next_account_info(accounts_iter)?; // 源账户所有者
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+
+        // ===== 账户验证 =====
+        check_writable(source_account)?;
+        check_signer(owner)?; // 所有者必须签名
+        check_token_program(token_program)?;
+
+        msg!("Revoking delegate of {}...", source_account.key);
+
+        // 关键API: revoke
+        // 功能: 创建撤销委托指令
+        // 参数:
+        //   token_program.key - 代币程序
+        //   source_account.key - 源代币账户
+        //   owner.key - 源账户所有者
+        //   &[] - 多签签名者（无）
+        invoke(
+            &revoke(token_program.key, source_account.key, owner.key, &[])?,
+            &[source_account.clone(), owner.clone(), token_program.clone()],
+        )?;
+
+        event::emit(&TokenEvent::Revoked {
+            account: *source_account.key,
+        });
+
+        // 成功日志
+        msg!("Delegate revoked successfully.");
+
+        Ok(())
+    }
+
+    /// 由委托人签名，代替所有者从源代币账户转账（校验小数位数），代币暂停期间拒绝
+    ///
+    /// 委托人须为源代币账户记录的委托人，且转账数量不超过剩余委托额度；
+    /// 目标关联令牌账户不存在时由支付账户出资创建
+    ///
+    /// # 参数
+    /// - `program_id`: 当前程序的ID
+    /// - `accounts`: 传入的账户列表
+    /// - `amount`: 转账数量
+    /// - `decimals`: 小数位数
+    ///
+    /// # 账户顺序要求
+    /// 1. 源代币账户 (可写)
+    /// 2. 铸币账户 (只读)
+    /// 3. 目标关联令牌账户 (可写)
+    /// 4. 目标账户所有者 (只读)
+    /// 5. 委托人账户 (签名)
+    /// 6. 支付账户 (签名，可写)
+    /// 7. 系统程序账户 (只读)
+    /// 8. 代币程序账户 (只读)
+    /// 9. 关联令牌账户程序 (只读)
+    /// 10. 代币配置 PDA 账户 (只读)
+    pub fn transfer_from(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        decimals: u8,
+    ) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let source_account = next_account_info(accounts_iter)?; // 源代币账户
+        let mint_account = next_account_info(accounts_iter)?; // 铸币账户
+        let destination_account = next_account_info(accounts_iter)?; // 目标关联令牌账户
+        let destination_owner = next_account_info(accounts_iter)?; // 目标账户所有者
+        let delegate = next_account_info(accounts_iter)?; // 委托人账户
+        let payer = next_account_info(accounts_iter)?; // 支付账户
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+        let config_account = next_account_info(accounts_iter)?; // 代币配置 PDA
+
+        // ===== 账户验证 =====
+        check_writable(source_account)?;
+        check_writable(destination_account)?;
+        check_signer(delegate)?; // 委托人必须签名
+        check_signer(payer)?;
+        check_writable(payer)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        check_not_paused(program_id, config_account, mint_account.key)?;
+        check_associated_token_address(
+            destination_account,
+            destination_owner.key,
+            mint_account.key,
+            token_program.key,
+        )?;
+
+        // 检查委托人与剩余委托额度
+        let source = unpack_token_account(source_account)?;
+        if source.delegate != COption::Some(*delegate.key) {
+            msg!(
+                "{} is not the delegate of {}",
+                delegate.key,
+                source_account.key
+            );
+            return Err(TokenError::InvalidDelegate.into());
+        }
+        if amount > source.delegated_amount {
+            msg!(
+                "Transfer amount {} exceeds delegated amount {}",
+                amount,
+                source.delegated_amount
+            );
+            return Err(TokenError::DelegatedAmountExceeded.into());
+        }
+
+        // 目标关联令牌账户不存在时创建
+        create_associated_token_account_if_missing(
+            payer,
+            destination_account,
+            destination_owner,
+            mint_account,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        msg!(
+            "Delegate {} transferring {} tokens from {} to {}...",
+            delegate.key,
+            amount,
+            source_account.key,
+            destination_account.key
+        );
+
+        // 委托人作为转账权限签名
+        transfer_tokens(
+            source_account,
+            mint_account,
+            destination_account,
+            delegate,
+            token_program,
+            amount,
+            decimals,
+            &[],
+        )?;
+
+        // 成功日志
+        msg!("Delegated transfer completed successfully.");
+
+        Ok(())
+    }
+
+    /// 将 SOL 包装为 wSOL
+    ///
+    /// 所有者的 wSOL 关联令牌账户不存在时由所有者出资创建，
+    /// 随后把 `lamports` 转入该账户并调用 `sync_native` 同步代币余额
+    ///
+    /// # 参数
+    /// - `accounts`: 传入的账户列表
+    /// - `lamports`: 包装的 SOL 数量（lamports）
+    ///
+    /// # 账户顺序要求
+    /// 1. 所有者账户 (签名，可写)
+    /// 2. 所有者的 wSOL 关联令牌账户 (可写)
+    /// 3. 原生 SOL 铸币账户 (只读，与代币程序对应)
+    /// 4. 系统程序账户 (只读)
+    /// 5. 代币程序账户 (只读)
+    /// 6. 关联令牌账户程序 (只读)
+    pub fn wrap_sol(accounts: &[AccountInfo], lamports: u64) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let owner = next_account_info(accounts_iter)?; // 所有者账户
+        let wsol_account = next_account_info(accounts_iter)?; // wSOL 关联令牌账户
+        let native_mint = next_account_info(accounts_iter)?; // 原生 SOL 铸币账户
+        let system_program = next_account_info(accounts_iter)?; // 系统程序
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+        let associated_token_program = next_account_info(accounts_iter)?; // 关联令牌账户程序
+
+        // ===== 账户验证 =====
+        check_signer(owner)?;
+        check_writable(owner)?;
+        check_writable(wsol_account)?;
+        check_system_program(system_program)?;
+        check_token_program(token_program)?;
+        check_associated_token_program(associated_token_program)?;
+        if native_mint.key != &native_mint_address(token_program.key) {
+            msg!(
+                "Expected native mint {}, got {}",
+                native_mint_address(token_program.key),
+                native_mint.key
+            );
+            return Err(TokenError::InvalidNativeMint.into());
+        }
+        check_associated_token_address(
+            wsol_account,
+            owner.key,
+            native_mint.key,
+            token_program.key,
+        )?;
+
+        // wSOL 关联令牌账户不存在时创建
+        create_associated_token_account_if_missing(
+            owner,
+            wsol_account,
+            owner,
+            native_mint,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+
+        msg!(
+            "Wrapping {} lamports into {}...",
+            lamports,
+            wsol_account.key
+        );
+
+        // 关键API: system_instruction::transfer
+        // 功能: 创建 SOL 转账指令
+        // 参数:
+        //   owner.key - 付款账户（所有者）
+        //   wsol_account.key - 收款账户（wSOL 关联令牌账户）
+        //   lamports - 转账金额
+        invoke(
+            &system_instruction::transfer(owner.key, wsol_account.key, lamports),
+            &[owner.clone(), wsol_account.clone(), system_program.clone()],
+        )?;
+
+        // 关键API: sync_native
+        // 功能: 按账户 lamports 同步原生代币账户的代币余额
+        // 参数:
+        //   token_program.key - 代币程序
+        //   wsol_account.key - wSOL 代币账户
+        invoke(
+            &sync_native(token_program.key, wsol_account.key)?,
+            &[wsol_account.clone(), token_program.clone()],
+        )?;
+
+        event::emit(&TokenEvent::SolWrapped {
+            owner: *owner.key,
+            account: *wsol_account.key,
+            lamports,
+        });
+
+        // 成功日志
+        msg!("SOL wrapped successfully.");
+
+        Ok(())
+    }
+
+    /// 解包 wSOL：关闭所有者的 wSOL 关联令牌账户，全部 lamports（包括租金）退回所有者
+    ///
+    /// # 参数
+    /// - `accounts`: 传入的账户列表
+    ///
+    /// # 账户顺序要求
+    /// 1. 所有者账户 (签名，可写)
+    /// 2. 所有者的 wSOL 关联令牌账户 (可写)
+    /// 3. 代币程序账户 (只读)
+    pub fn unwrap_sol(accounts: &[AccountInfo]) -> ProgramResult {
+        // 创建账户迭代器
+        let accounts_iter = &mut accounts.iter();
+
+        // 按顺序解析账户
+        let owner = next_account_info(accounts_iter)?; // 所有者账户
+        let wsol_account = next_account_info(accounts_iter)?; // wSOL 关联令牌账户
+        let token_program = next_account_info(accounts_iter)?; // 代币程序
+
+        // ===== 账户验证 =====
+        check_signer(owner)?;
+        check_writable(owner)?;
+        check_writable(wsol_account)?;
+        check_token_program(token_program)?;
+        check_associated_token_address(
+            wsol_account,
+            owner.key,
+            &native_mint_address(token_program.key),
+            token_program.key,
+        )?;
+
+        let lamports = wsol_account.lamports();
+        msg!(
+            "Unwrapping {} lamports from {}...",
+            lamports,
+            wsol_account.key
+        );
+
+        // 关键API: close_account
+        // 功能: 关闭 wSOL 账户，lamports 全部退回所有者
+        invoke(
+            &close_account(
+                token_program.key,
+                wsol_account.key,
+                owner.key,
+                owner.key,
+                &[],
+            )?,
+            &[wsol_account.clone(), owner.clone(), token_program.clone()],
+        )?;
+
+        event::emit(&TokenEvent::SolUnwrapped {
+            owner: *owner.key,
+            account: *wsol_account.key,
+            lamports,
+        });
+
+        // 成功日志
+        msg!("SOL unwrapped successfully.");
+
+        Ok(())
+    }
+}
+
+/// 检查账户已签名
+fn check_signer(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer {
+        msg!("Account {} must sign", account.key);
+        return Err(TokenError::MissingSigner.into());
+    }
+    Ok(())
+}
+
+/// 检查账户可写
+fn check_writable(account: &AccountInfo) -> ProgramResult {
+    if !account.is_writable {
+        msg!("Account {} must be writable", account.key);
+        return Err(TokenError::AccountNotWritable.into());
+    }
+    Ok(())
+}
+
+/// 检查系统程序账户
+fn check_system_program(account: &AccountInfo) -> ProgramResult {
+    if account.key != &system_program::id() {
+        msg!(
+            "Invalid system program: expected {}, got {}",
+            system_program::id(),
+            account.key
+        );
+        return Err(TokenError::WrongSystemProgram.into());
+    }
+    Ok(())
+}
+
+/// 检查代币程序账户（SPL Token 或 Token-2022）
+fn check_token_program(account: &AccountInfo) -> ProgramResult {
+    if account.key != &spl_token::id() && account.key != &spl_token_2022::id() {
+        msg!(
+            "Invalid token program: expected {} or {}, got {}",
+            spl_token::id(),
+            spl_token_2022::id(),
+            account.key
+        );
+        return Err(TokenError::WrongTokenProgram.into());
+    }
+    Ok(())
+}
+
+/// 检查关联令牌账户程序账户
+fn check_associated_token_program(account: &AccountInfo) -> ProgramResult {
+    if account.key != &spl_associated_token_account::id() {
+        msg!(
+            "Invalid associated token program: expected {}, got {}",
+            spl_associated_token_account::id(),
+            account.key
+        );
+        return Err(TokenError::WrongAssociatedTokenProgram.into());
+    }
+    Ok(())
+}
+
+/// 检查租金系统变量账户
+fn check_rent_sysvar(account: &AccountInfo) -> ProgramResult {
+    if account.key != &sysvar::rent::id() {
+        msg!(
+            "Invalid rent sysvar: expected {}, got {}",
+            sysvar::rent::id(),
+            account.key
+        );
+        return Err(TokenError::WrongRentSysvar.into());
+    }
+    Ok(())
+}
+
+/// 检查关联令牌账户地址等于 get_associated_token_address(owner, mint)
+fn check_associated_token_address(
+    account: &AccountInfo,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> ProgramResult {
+    let expected = get_associated_token_address_with_program_id(owner, mint, token_program_id);
+    if account.key != &expected {
+        msg!(
+            "Invalid associated token account: expected {}, got {}",
+            expected,
+            account.key
+        );
+        return Err(TokenError::InvalidAssociatedTokenAccount.into());
+    }
+    Ok(())
+}
+
+/// 解析铸币账户状态，未初始化（或无法解析）时返回 `TokenError::UninitializedMint`
+///
+/// 同时支持旧版 SPL Token 与带扩展的 Token-2022 铸币账户
+fn unpack_mint(mint_account: &AccountInfo) -> Result<Mint, ProgramError> {
+    StateWithExtensions::<Mint>::unpack(&mint_account.data.borrow())
+        .map(|state| state.base)
+        .map_err(|_| {
+            msg!("Mint {} is not initialized", mint_account.key);
+            TokenError::UninitializedMint.into()
+        })
+}
+
+/// 解析代币账户状态（同时支持 SPL Token 与 Token-2022）
+fn unpack_token_account(token_account: &AccountInfo) -> Result<Account, ProgramError> {
+    StateWithExtensions::<Account>::unpack(&token_account.data.borrow()).map(|state| state.base)
+}
+
+/// 读取并校验代币配置 PDA ["config", mint]
+///
+/// # 参数
+/// - `program_id`: 当前程序的ID
+/// - `config_account`: 代币配置账户
+/// - `mint`: 铸币账户地址
+fn load_config(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<TokenConfig, ProgramError> {
+    let (config_pda, _) = find_config_address(program_id, mint);
+    if config_account.key != &config_pda || config_account.owner != program_id {
+        msg!("Invalid config account: expected {}", config_pda);
+        return Err(TokenError::InvalidConfigAccount.into());
+    }
+    Ok(TokenConfig::try_from_slice(&config_account.data.borrow())?)
+}
+
+/// 检查代币未暂停
+///
+/// 代币配置 PDA 不存在（铸币账户不是由本程序创建）时视为不可暂停
+///
+/// # 参数
+/// - `program_id`: 当前程序的ID
+/// - `config_account`: 代币配置 PDA
+/// - `mint`: 铸币账户地址
+fn check_not_paused(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+    mint: &Pubkey,
+) -> ProgramResult {
+    let (config_pda, _) = find_config_address(program_id, mint);
+    if config_account.key == &config_pda && config_account.data_is_empty() {
+        return Ok(());
+    }
+    if load_config(program_id, config_account, mint)?.paused {
+        msg!("Token {} is paused", mint);
+        return Err(TokenError::TokenPaused.into());
+    }
+    Ok(())
+}
+
+/// 读取代币配置，并检查 `admin` 已签名且是代币配置中的管理员
+///
+/// # 参数
+/// - `program_id`: 当前程序的ID
+/// - `config_account`: 代币配置 PDA
+/// - `mint`: 铸币账户地址
+/// - `admin`: 签名者
+fn load_config_as_admin(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+    mint: &Pubkey,
+    admin: &AccountInfo,
+) -> Result<TokenConfig, ProgramError> {
+    check_signer(admin)?;
+    let config = load_config(program_id, config_account, mint)?;
+    if admin.key != &config.admin {
+        msg!(
+            "Signer {} is not the token admin {}",
+            admin.key,
+            config.admin
+        );
+        return Err(TokenError::InvalidAdmin.into());
+    }
+    Ok(config)
+}
+
+/// 读取并校验质押池 PDA ["stake_pool", mint]
+fn load_stake_pool(
+    program_id: &Pubkey,
+    pool_account: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<StakePool, ProgramError> {
+    let (pool_pda, _) = find_stake_pool_address(program_id, mint);
+    if pool_account.key != &pool_pda || pool_account.owner != program_id {
+        msg!("Invalid stake pool account: expected {}", pool_pda);
+        return Err(TokenError::InvalidStakeAccount.into());
+    }
+    Ok(StakePool::try_from_slice(&pool_account.data.borrow())?)
+}
+
+/// 读取并校验用户质押 PDA ["stake", pool, owner]
+fn load_user_stake(
+    program_id: &Pubkey,
+    user_stake_account: &AccountInfo,
+    pool: &Pubkey,
+    owner: &Pubkey,
+) -> Result<UserStake, ProgramError> {
+    let (user_stake_pda, _) = find_user_stake_address(program_id, pool, owner);
+    if user_stake_account.key != &user_stake_pda || user_stake_account.owner != program_id {
+        msg!("Invalid user stake account: expected {}", user_stake_pda);
+        return Err(TokenError::InvalidStakeAccount.into());
+    }
+    Ok(UserStake::try_from_slice(
+        &user_stake_account.data.borrow(),
+    )?)
+}
+
+/// 检查签名者是铸币账户的铸币权限
+///
+/// 铸币权限为程序 PDA ["mint_authority", mint] 时，由代币配置中的管理员代为签名；
+/// 铸币权限为 spl-token 多签账户时，`authority` 传入多签账户本身，
+/// 由 `multisig_signers` 中至少 M 个不同的多签签名者签名
+///
+/// # 参数
+/// - `program_id`: 当前程序的ID
+/// - `mint_account`: 铸币账户
+/// - `config_account`: 代币配置 PDA
+/// - `authority`: 签名者（或多签账户）
+/// - `multisig_signers`: 多签签名者账户（非多签时为空）
+fn check_mint_authority_signer(
+    program_id: &Pubkey,
+    mint_account: &AccountInfo,
+    config_account: &AccountInfo,
+    authority: &AccountInfo,
+    multisig_signers: &[AccountInfo],
+) -> ProgramResult {
+    if let Some(multisig) = unpack_multisig(authority) {
+        if unpack_mint(mint_account)?.mint_authority != COption::Some(*authority.key) {
+            msg!(
+                "Multisig {} is not the mint authority of {}",
+                authority.key,
+                mint_account.key
+            );
+            return Err(TokenError::InvalidMintAuthority.into());
+        }
+        return check_multisig_signers(&multisig, multisig_signers);
+    }
+    check_signer(authority)?;
+
+    let (mint_authority_pda, _) = find_mint_authority_address(program_id, mint_account.key);
+    let expected = match unpack_mint(mint_account)?.mint_authority {
+        COption::Some(mint_authority) if mint_authority == mint_authority_pda => {
+            load_config(program_id, config_account, mint_account.key)?.admin
+        }
+        COption::Some(mint_authority) => mint_authority,
+        COption::None => {
+            msg!("Mint {} has no mint authority", mint_account.key);
+            return Err(TokenError::InvalidMintAuthority.into());
+        }
+    };
+    if authority.key != &expected {
+        msg!(
+            "Signer {} is not the mint authority {}",
+            authority.key,
+            expected
+        );
+        return Err(TokenError::InvalidMintAuthority.into());
+    }
+    Ok(())
+}
+
+/// 解析 spl-token 多签账户（不是已初始化的多签账户时返回 `None`）
+fn unpack_multisig(account: &AccountInfo) -> Option<Multisig> {
+    if account.owner != &spl_token::id() && account.owner != &spl_token_2022::id() {
+        return None;
+    }
+    Multisig::unpack(&account.data.borrow()).ok()
+}
+
+/// 检查多签签名者集合满足 M-of-N
+///
+/// 每个多签签名者最多计数一次，与代币程序校验多签的规则一致
+fn check_multisig_signers(multisig: &Multisig, signers: &[AccountInfo]) -> ProgramResult {
+    let mut matched = [false; spl_token_2022::instruction::MAX_SIGNERS];
+    let mut num_signers = 0;
+    for signer in signers.iter().filter(|signer| signer.is_signer) {
+        let valid_signers = &multisig.signers[..multisig.n as usize];
+        for (position, key) in valid_signers.iter().enumerate() {
+            if key == signer.key && !matched[position] {
+                matched[position] = true;
+                num_signers += 1;
+                break;
+            }
+        }
+    }
+    if num_signers < multisig.m {
+        msg!(
+            "Multisig requires {} signers, got {}",
+            multisig.m,
+            num_signers
+        );
+        return Err(TokenError::InvalidMintAuthority.into());
+    }
+    Ok(())
+}
+
+/// 检查铸币账户的铸币权限是程序 PDA ["mint_authority", mint]
+///
+/// 空投等由程序自动铸造的场景要求代币以程序 PDA 作为铸币权限创建
+fn check_program_mint_authority(program_id: &Pubkey, mint_account: &AccountInfo) -> ProgramResult {
+    let (mint_authority_pda, _) = find_mint_authority_address(program_id, mint_account.key);
+    if unpack_mint(mint_account)?.mint_authority != COption::Some(mint_authority_pda) {
+        msg!(
+            "Mint authority of {} is not the program PDA {}",
+            mint_account.key,
+            mint_authority_pda
+        );
+        return Err(TokenError::InvalidMintAuthority.into());
+    }
+    Ok(())
+}
+
+/// 检查本次铸造是否被允许
+///
+/// - 铸币权限为程序 PDA 时，支付账户必须签名，且须为代币配置中的管理员，
+///   或提供自己的白名单 PDA 且剩余额度足够（额度随之扣减）
+/// - 代币未暂停，且铸造后的发行量不得超过代币配置中的最大发行量
+///
+/// # 参数
+/// - `program_id`: 当前程序的ID
+/// - `mint_account`: 铸币账户
+/// - `mint_authority`: 铸币权限账户
+/// - `payer`: 支付账户（调用者）
+/// - `config_account`: 代币配置 PDA
+/// - `allowlist_account`: 支付账户的白名单 PDA（可选）
+/// - `amount`: 本次铸造总量
+fn check_mint_allowed(
+    program_id: &Pubkey,
+    mint_account: &AccountInfo,
+    mint_authority: &AccountInfo,
+    payer: &AccountInfo,
+    config_account: &AccountInfo,
+    allowlist_account: Option<&AccountInfo>,
+    amount: u64,
+) -> ProgramResult {
+    // 读取代币配置
+    let config = load_config(program_id, config_account, mint_account.key)?;
+
+    // 铸币权限为程序 PDA 时，程序会代为签名 mint_to，调用者必须自行签名；
+    // 非管理员只能在白名单额度内铸造
+    let (mint_authority_pda, _) = find_mint_authority_address(program_id, mint_account.key);
+    if mint_authority.key == &mint_authority_pda {
+        check_signer(payer)?;
+    }
+    if mint_authority.key == &mint_authority_pda && payer.key != &config.admin {
+        let Some(allowlist_account) = allowlist_account else {
+            msg!(
+                "Only admin {} or allowlisted wallets can mint with program authority.",
+                config.admin
+            );
+            return Err(TokenError::InvalidAllowlistAccount.into());
+        };
+        consume_allowance(
+            program_id,
+            allowlist_account,
+            mint_account.key,
+            payer.key,
+            amount,
+        )?;
+    }
+
+    // 检查暂停状态与最大发行量
+    check_can_mint(&config, mint_account, amount)
+}
+
+/// 从钱包的白名单 PDA 中扣减 `amount` 额度
+///
+/// # 参数
+/// - `program_id`: 当前程序的ID
+/// - `allowlist_account`: 白名单 PDA ["allowlist", mint, wallet]
+/// - `mint`: 铸币账户地址
+/// - `wallet`: 白名单钱包
+/// - `amount`: 本次铸造数量
+fn consume_allowance(
+    program_id: &Pubkey,
+    allowlist_account: &AccountInfo,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let (allowlist_pda, _) = find_allowlist_address(program_id, mint, wallet);
+    if allowlist_account.key != &allowlist_pda || allowlist_account.owner != program_id {
+        msg!(
+            "Wallet {} is not allowlisted (expected {})",
+            wallet,
+            allowlist_pda
+        );
+        return Err(TokenError::InvalidAllowlistAccount.into());
+    }
+    check_writable(allowlist_account)?;
+
+    let mut entry = AllowlistEntry::try_from_slice(&allowlist_account.data.borrow())?;
+    if amount > entry.allowance {
+        msg!(
+            "Minting {} would exceed allowance {} of {}",
+            amount,
+            entry.allowance,
+            wallet
+        );
+        return Err(TokenError::AllowanceExceeded.into());
+    }
+    entry.allowance -= amount;
+    entry.serialize(&mut &mut allowlist_account.try_borrow_mut_data()?[..])?;
+    msg!("Remaining allowance of {}: {}", wallet, entry.allowance);
+    Ok(())
+}
+
+/// 检查代币未暂停，且在当前发行量上再铸造 `amount` 不超过代币配置中的最大发行量
+fn check_can_mint(config: &TokenConfig, mint_account: &AccountInfo, amount: u64) -> ProgramResult {
+    if config.paused {
+        msg!("Token {} is paused", mint_account.key);
+        return Err(TokenError::TokenPaused.into());
+    }
+    let supply = unpack_mint(mint_account)?.supply;
+    if !config.can_mint(supply, amount) {
+        msg!(
+            "Minting {} would exceed max supply {} (current supply {})",
+            amount,
+            config.max_supply,
+            supply
+        );
+        return Err(TokenError::MaxSupplyExceeded.into());
+    }
+    Ok(())
+}
+
+/// 创建由本程序拥有的 PDA 账户
+///
+/// # 参数
+/// - `program_id`: 当前程序的ID（新账户的所有者）
+/// - `payer`: 支付账户（签名）
+/// - `pda_account`: 待创建的 PDA 账户
+/// - `system_program`: 系统程序
+/// - `space`: 账户大小
+/// - `signer_seeds`: PDA 的签名种子（包含 bump seed）
+fn create_pda_account<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    pda_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    // 计算所需租金（lamports）
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    // 执行带签名的跨程序调用（创建账户）
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,       // 支付账户
+            pda_account.key, // 新账户地址
+            lamports,        // 租金金额
+            space as u64,    // 账户空间
+            program_id,      // 所有者程序
+        ),
+        &[payer.clone(), pda_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )
+}
+
+/// 创建并初始化 M-of-N 的 spl-token `Multisig` 账户
+///
+/// 多签账户地址为 PDA ["multisig", mint]，由程序签名创建，任何单个密钥都不能代表多签签名
+///
+/// # 参数
+/// - `program_id`: 当前程序的ID
+/// - `mint`: 铸币账户地址
+/// - `payer`: 支付账户（签名）
+/// - `multisig_account`: 多签 PDA 账户
+/// - `token_program`: 代币程序
+/// - `system_program`: 系统程序
+/// - `signers`: N 个签名者账户
+/// - `m`: 门限 M
+#[allow(clippy::too_many_arguments)]
+fn create_multisig<'a>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    payer: &AccountInfo<'a>,
+    multisig_account: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    signers: &[AccountInfo<'a>],
+    m: u8,
+) -> ProgramResult {
+    check_writable(multisig_account)?;
+    let (multisig_pda, bump_seed) = find_multisig_address(program_id, mint);
+    if multisig_account.key != &multisig_pda {
+        msg!("Invalid multisig account: expected {}", multisig_pda);
+        return Err(TokenError::InvalidMultisig.into());
+    }
+    if m == 0
+        || m as usize > signers.len()
+        || signers.len() > spl_token_2022::instruction::MAX_SIGNERS
+    {
+        msg!("Invalid multisig: {} of {} signers", m, signers.len());
+        return Err(TokenError::InvalidMultisig.into());
+    }
+
+    msg!(
+        "Creating {}-of-{} multisig mint authority...",
+        m,
+        signers.len()
+    );
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            multisig_account.key,
+            Rent::get()?.minimum_balance(Multisig::LEN),
+            Multisig::LEN as u64,
+            token_program.key, // 账户所有者（代币程序）
+        ),
+        &[
+            payer.clone(),
+            multisig_account.clone(),
+            system_program.clone(),
+        ],
+        &[&[MULTISIG_SEED, mint.as_ref(), &[bump_seed]]],
+    )?;
+
+    // 关键API: initialize_multisig2
+    // 功能: 初始化多签账户（不需要租金系统变量）
+    // 参数:
+    //   token_program.key - 代币程序
+    //   multisig_account.key - 多签账户
+    //   &signer_pubkeys - N 个签名者
+    //   m - 门限 M
+    let signer_pubkeys: Vec<&Pubkey> = signers.iter().map(|signer| signer.key).collect();
+    let mut account_infos = vec![multisig_account.clone()];
+    account_infos.extend(signers.iter().cloned());
+    account_infos.push(token_program.clone());
+    invoke(
+        &initialize_multisig2(token_program.key, multisig_account.key, &signer_pubkeys, m)?,
+        &account_infos,
+    )
+}
+
+/// 铸造代币到目标代币账户
+///
+/// 铸币权限为程序 PDA ["mint_authority", mint] 时使用 `invoke_signed` 由程序签名，
+/// 铸币权限为 spl-token 多签账户时传入签名者集合，由代币程序校验 M-of-N，
+/// 否则要求铸币权限账户自身签名
+///
+/// # 参数
+/// - `program_id`: 当前程序的ID
+/// - `mint_account`: 铸币账户
+/// - `destination`: 目标代币账户
+/// - `mint_authority`: 铸币权限账户
+/// - `token_program`: 代币程序
+/// - `amount`: 铸造数量
+/// - `multisig_signers`: 多签铸币权限的签名者集合（非多签时为空）
+fn mint_tokens<'a>(
+    program_id: &Pubkey,
+    mint_account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    mint_authority: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+    multisig_signers: &[AccountInfo<'a>],
+) -> ProgramResult {
+    // 关键API: mint_to
+    // 功能: 创建铸造代币指令
+    // 参数:
+    //   token_program.key - 代币程序
+    //   mint_account.key - 铸币账户
+    //   destination.key - 目标账户
+    //   mint_authority.key - 铸造权限
+    //   &signer_pubkeys - 多签签名者（非多签时为空）
+    //   amount - 铸造数量
+    let signer_pubkeys: Vec<&Pubkey> = multisig_signers.iter().map(|signer| signer.key).collect();
+    let ix = mint_to(
+        token_program.key,
+        mint_account.key,
+        destination.key,
+        mint_authority.key, // 铸造权限
+        &signer_pubkeys,
+        amount,
+    )?;
+    let mut account_infos = vec![
+        mint_account.clone(),
+        destination.clone(),
+        mint_authority.clone(),
+        token_program.clone(),
+    ];
+    account_infos.extend(multisig_signers.iter().cloned());
+
+    // 铸币权限是程序 PDA：由程序使用种子签名
+    let (mint_authority_pda, bump_seed) = find_mint_authority_address(program_id, mint_account.key);
+    if mint_authority.key == &mint_authority_pda {
+        msg!("Signing mint_to with program mint authority PDA...");
+        invoke_signed(
+            &ix,
+            &account_infos,
+            &[&[
+                MINT_AUTHORITY_SEED,       // 固定种子
+                mint_account.key.as_ref(), // 铸币账户
+                &[bump_seed],              // bump seed
+            ]],
+        )?;
+    } else if !multisig_signers.is_empty() {
+        // 多签铸币权限：由代币程序校验签名者集合满足 M-of-N
+        msg!(
+            "Minting with {} multisig signers...",
+            multisig_signers.len()
+        );
+        invoke(&ix, &account_infos)?;
+    } else {
+        // 普通铸币权限：必须由铸币权限账户签名
+        check_signer(mint_authority)?;
+        invoke(&ix, &account_infos)?;
+    }
+
+    event::emit(&TokenEvent::Minted {
+        mint: *mint_account.key,
+        destination: *destination.key,
+        amount,
+    });
+    Ok(())
+}
+
+/// 在两个代币账户之间转账（transfer_checked）
+///
+/// `signer_seeds` 为空时要求 `authority` 自身签名，否则由程序使用 PDA 种子签名
+///
+/// # 参数
+/// - `source`: 源代币账户
+/// - `mint_account`: 铸币账户
+/// - `destination`: 目标代币账户
+/// - `authority`: 源账户所有者或委托人（或 PDA）
+/// - `token_program`: 代币程序
+/// - `amount`: 转账数量
+/// - `decimals`: 小数位数
+/// - `signer_seeds`: PDA 签名种子
+#[allow(clippy::too_many_arguments)]
+fn transfer_tokens<'a>(
+    source: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    // 关键API: transfer_checked
+    // 功能: 创建带小数位数校验的转账指令
+    // 参数:
+    //   token_program.key - 代币程序
+    //   source.key - 源代币账户
+    //   mint_account.key - 铸币账户（用于校验小数位数）
+    //   destination.key - 目标代币账户
+    //   authority.key - 源账户所有者或委托人
+    //   &[] - 多签签名者（无）
+    //   amount - 转账数量
+    //   decimals - 小数位数
+    invoke_signed(
+        &transfer_checked(
+            token_program.key,
+            source.key,
+            mint_account.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[
+            source.clone(),
+            mint_account.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    event::emit(&TokenEvent::Transferred {
+        mint: *mint_account.key,
+        source: *source.key,
+        destination: *destination.key,
+        amount,
+    });
+    Ok(())
+}
+
+/// 关闭报价的金库关联令牌账户与报价 PDA，租金退回做市方
+///
+/// # 参数
+/// - `offer_account`: 报价 PDA（金库所有者）
+/// - `vault`: 金库关联令牌账户（余额须为 0）
+/// - `maker`: 做市方（接收租金）
+/// - `token_program`: 代币程序
+/// - `offer_seeds`: 报价 PDA 的签名种子（包含 bump seed）
+fn close_vault_and_offer<'a>(
+    offer_account: &AccountInfo<'a>,
+    vault: &AccountInfo<'a>,
+    maker: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    offer_seeds: &[&[u8]],
+) -> ProgramResult {
+    // 关键API: close_account（由报价 PDA 签名）
+    invoke_signed(
+        &close_account(
+            token_program.key,
+            vault.key,
+            maker.key,
+            offer_account.key,
+            &[],
+        )?,
+        &[
+            vault.clone(),
+            maker.clone(),
+            offer_account.clone(),
+            token_program.clone(),
+        ],
+        &[offer_seeds],
+    )?;
+
+    // 关闭程序拥有的报价账户
+    close_program_account(offer_account, maker)
+}
+
+/// 关闭本程序拥有的账户：清空数据并将全部 lamports 转给 `destination`，交易结束后由运行时回收
+fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **account.try_borrow_mut_lamports()? -= lamports;
+    **destination.try_borrow_mut_lamports()? += lamports;
+    account.try_borrow_mut_data()?.fill(0);
+    Ok(())
+}
+
+/// 关联令牌账户不存在时（lamports 为 0）创建该账户
+///
+/// # 参数
+/// - `payer`: 支付账户（签名）
+/// - `associated_token_account`: 待检查 / 创建的关联令牌账户
+/// - `owner`: 关联令牌账户的所有者
+/// - `mint_account`: 铸币账户
+/// - `system_program`: 系统程序
+/// - `token_program`: 代币程序
+/// - `associated_token_program`: 关联令牌账户程序
+fn create_associated_token_account_if_missing<'a>(
+    payer: &AccountInfo<'a>,
+    associated_token_account: &AccountInfo<'a>,
+    owner: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    associated_token_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    // 检查关联令牌账户是否存在（通过lamports判断）
+    if associated_token_account.lamports() != 0 {
+        msg!("Associated token account exists.");
+        return Ok(());
+    }
+
+    // 如果不存在，创建关联令牌账户
+    msg!("Creating associated token account...");
+
+    // 关键API: spl_associated_token_account::instruction::create_associated_token_account
+    // 功能: 创建关联令牌账户指令
+    // 参数:
+    //   payer.key - 支付账户
+    //   owner.key - 代币所有者
+    //   mint_account.key - 铸币账户
+    //   token_program.key - 代币程序
+    invoke(
+        &spl_associated_token_account::instruction::create_associated_token_account(
+            payer.key,
+            owner.key, // 代币所有者
+            mint_account.key,
+            token_program.key,
+        ),
+        &[
+            payer.clone(),
+            associated_token_account.clone(),
+            owner.clone(),
+            mint_account.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )
+}
@@ -0,0 +1,420 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::error::TokenError;
+
+/// 每个铸币账户对应的配置账户（PDA：["config", mint]）
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct TokenConfig {
+    pub admin: Pubkey,   // 管理员（创建代币时的支付账户）
+    pub decimals: u8,    // 小数位数
+    pub max_supply: u64, // 最大发行量
+    pub paused: bool,    // 是否暂停（暂停期间拒绝铸造、转账和销毁）
+}
+
+impl TokenConfig {
+    // 账户大小：管理员(32) + 小数位数(1) + 最大发行量(8) + 暂停标志(1)
+    pub const LEN: usize = 32 + 1 + 8 + 1;
+
+    pub fn new(admin: Pubkey, decimals: u8, max_supply: u64) -> Self {
+        Self {
+            admin,
+            decimals,
+            max_supply,
+            paused: false,
+        }
+    }
+
+    /// 检查在当前发行量上再铸造 `amount` 是否超过最大发行量
+    pub fn can_mint(&self, supply: u64, amount: u64) -> bool {
+        supply
+            .checked_add(amount)
+            .is_some_and(|new_supply| new_supply <= self.max_supply)
+    }
+}
+
+// 元数据字段的最大长度（字节）
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
+
+/// 代币元数据账户（PDA：["metadata", mint]）
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct TokenMetadata {
+    pub mint: Pubkey,   // 铸币账户
+    pub name: String,   // 名称
+    pub symbol: String, // 符号
+    pub uri: String,    // 元数据 URI（如 JSON 链接）
+}
+
+impl TokenMetadata {
+    // 账户大小按字段最大长度分配，更新时无需重新分配空间：
+    // 铸币账户(32) + 名称(4 + 32) + 符号(4 + 10) + URI(4 + 200)
+    pub const LEN: usize = 32 + (4 + MAX_NAME_LEN) + (4 + MAX_SYMBOL_LEN) + (4 + MAX_URI_LEN);
+
+    pub fn new(mint: Pubkey, name: String, symbol: String, uri: String) -> Self {
+        Self {
+            mint,
+            name,
+            symbol,
+            uri,
+        }
+    }
+
+    /// 检查各字段长度是否在限制之内
+    pub fn is_valid(&self) -> bool {
+        self.name.len() <= MAX_NAME_LEN
+            && self.symbol.len() <= MAX_SYMBOL_LEN
+            && self.uri.len() <= MAX_URI_LEN
+    }
+}
+
+/// 线性归属计划（PDA：["vesting", mint, beneficiary]）
+///
+/// 代币锁定在该 PDA 拥有的托管关联令牌账户中，
+/// 悬崖时间之前不可领取，之后按 [start_ts, end_ts] 线性解锁
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct VestingSchedule {
+    pub mint: Pubkey,        // 铸币账户
+    pub beneficiary: Pubkey, // 受益人
+    pub total: u64,          // 锁定总量
+    pub claimed: u64,        // 已领取数量
+    pub start_ts: i64,       // 开始时间（Unix 时间戳）
+    pub cliff_ts: i64,       // 悬崖时间
+    pub end_ts: i64,         // 结束时间（全部解锁）
+}
+
+impl VestingSchedule {
+    // 账户大小：铸币账户(32) + 受益人(32) + 总量(8) + 已领取(8) + 三个时间戳(8 * 3)
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 * 3;
+
+    pub fn new(
+        mint: Pubkey,
+        beneficiary: Pubkey,
+        total: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+    ) -> Self {
+        Self {
+            mint,
+            beneficiary,
+            total,
+            claimed: 0,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        }
+    }
+
+    /// 检查时间参数：start_ts <= cliff_ts <= end_ts 且 start_ts < end_ts
+    pub fn is_valid(&self) -> bool {
+        self.total > 0
+            && self.start_ts <= self.cliff_ts
+            && self.cliff_ts <= self.end_ts
+            && self.start_ts < self.end_ts
+    }
+
+    /// 计算到 `now` 为止已解锁的总量
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now < self.cliff_ts {
+            return 0;
+        }
+        if now >= self.end_ts {
+            return self.total;
+        }
+        // total * (now - start) / (end - start)，使用 u128 避免溢出
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        (self.total as u128 * elapsed / duration) as u64
+    }
+
+    /// 计算到 `now` 为止可领取的数量（已解锁 - 已领取）
+    pub fn claimable_amount(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.claimed)
+    }
+}
+
+/// 默克尔空投分发器（PDA：["distributor", mint]）
+///
+/// 领取状态保存在单独的位图 PDA ["claim_bitmap", distributor] 中，每个叶子占 1 位
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct MerkleDistributor {
+    pub mint: Pubkey,          // 铸币账户
+    pub admin: Pubkey,         // 发布者（代币配置中的管理员）
+    pub merkle_root: [u8; 32], // 默克尔根
+    pub num_nodes: u64,        // 叶子数量
+    pub num_claimed: u64,      // 已领取的叶子数量
+    pub total_claimed: u64,    // 已领取的代币总量
+}
+
+impl MerkleDistributor {
+    // 账户大小：铸币账户(32) + 管理员(32) + 默克尔根(32) + 三个计数(8 * 3)
+    pub const LEN: usize = 32 + 32 + 32 + 8 * 3;
+
+    pub fn new(mint: Pubkey, admin: Pubkey, merkle_root: [u8; 32], num_nodes: u64) -> Self {
+        Self {
+            mint,
+            admin,
+            merkle_root,
+            num_nodes,
+            num_claimed: 0,
+            total_claimed: 0,
+        }
+    }
+
+    /// 领取位图账户大小（每个叶子 1 位，向上取整）
+    pub fn bitmap_len(num_nodes: u64) -> usize {
+        num_nodes.div_ceil(8) as usize
+    }
+}
+
+/// 水龙头配置（PDA：["faucet", mint]）
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct FaucetConfig {
+    pub mint: Pubkey,            // 铸币账户
+    pub amount_per_request: u64, // 每次领取数量
+    pub cooldown_slots: u64,     // 同一钱包两次领取之间的冷却时间（slot 数）
+}
+
+impl FaucetConfig {
+    // 账户大小：铸币账户(32) + 每次领取数量(8) + 冷却时间(8)
+    pub const LEN: usize = 32 + 8 + 8;
+
+    pub fn new(mint: Pubkey, amount_per_request: u64, cooldown_slots: u64) -> Self {
+        Self {
+            mint,
+            amount_per_request,
+            cooldown_slots,
+        }
+    }
+}
+
+/// 钱包的水龙头领取记录（PDA：["faucet_claim", faucet, wallet]）
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct FaucetClaim {
+    pub wallet: Pubkey,       // 领取钱包
+    pub last_claim_slot: u64, // 上次领取的 slot
+}
+
+impl FaucetClaim {
+    // 账户大小：领取钱包(32) + 上次领取 slot(8)
+    pub const LEN: usize = 32 + 8;
+
+    pub fn new(wallet: Pubkey, last_claim_slot: u64) -> Self {
+        Self {
+            wallet,
+            last_claim_slot,
+        }
+    }
+
+    /// 冷却时间结束后的第一个可领取 slot
+    pub fn next_claim_slot(&self, cooldown_slots: u64) -> u64 {
+        self.last_claim_slot.saturating_add(cooldown_slots)
+    }
+}
+
+/// 两种代币之间的交换报价（PDA：["offer", maker, offered_mint, wanted_mint]）
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct Offer {
+    pub maker: Pubkey,        // 做市方
+    pub offered_mint: Pubkey, // 提供的代币
+    pub offered_amount: u64,  // 提供数量（锁定在金库中）
+    pub wanted_mint: Pubkey,  // 想要的代币
+    pub wanted_amount: u64,   // 想要的数量
+}
+
+impl Offer {
+    // 账户大小：做市方(32) + 提供的代币(32) + 提供数量(8) + 想要的代币(32) + 想要的数量(8)
+    pub const LEN: usize = 32 + 32 + 8 + 32 + 8;
+
+    pub fn new(
+        maker: Pubkey,
+        offered_mint: Pubkey,
+        offered_amount: u64,
+        wanted_mint: Pubkey,
+        wanted_amount: u64,
+    ) -> Self {
+        Self {
+            maker,
+            offered_mint,
+            offered_amount,
+            wanted_mint,
+            wanted_amount,
+        }
+    }
+}
+
+/// 公开发售配置（PDA：["sale", mint]）
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct SaleConfig {
+    pub mint: Pubkey,            // 铸币账户
+    pub price_lamports: u64,     // 每个完整代币（10^decimals 最小单位）的价格（lamports）
+    pub treasury: Pubkey,        // 收款的国库账户
+    pub wallet_cap: Option<u64>, // 单个钱包的购买上限（最小单位），None 表示不限
+}
+
+impl SaleConfig {
+    // 账户大小：铸币账户(32) + 价格(8) + 国库账户(32) + 购买上限(1 + 8)
+    pub const LEN: usize = 32 + 8 + 32 + 1 + 8;
+
+    pub fn new(
+        mint: Pubkey,
+        price_lamports: u64,
+        treasury: Pubkey,
+        wallet_cap: Option<u64>,
+    ) -> Self {
+        Self {
+            mint,
+            price_lamports,
+            treasury,
+            wallet_cap,
+        }
+    }
+
+    /// 计算购买 `amount`（最小单位）所需的 lamports，向上取整；溢出时返回 None
+    pub fn cost(&self, amount: u64, decimals: u8) -> Option<u64> {
+        let unit = 10u128.checked_pow(decimals as u32)?;
+        let cost = (amount as u128 * self.price_lamports as u128).div_ceil(unit);
+        u64::try_from(cost).ok()
+    }
+
+    /// 检查已购买 `purchased` 后再购买 `amount` 是否超过单个钱包上限
+    pub fn within_cap(&self, purchased: u64, amount: u64) -> bool {
+        match self.wallet_cap {
+            Some(cap) => purchased
+                .checked_add(amount)
+                .is_some_and(|total| total <= cap),
+            None => true,
+        }
+    }
+}
+
+/// 钱包在某发售下的购买记录（PDA：["sale_purchase", sale, buyer]）
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct SalePurchase {
+    pub buyer: Pubkey,  // 购买钱包
+    pub purchased: u64, // 累计购买数量（最小单位）
+}
+
+impl SalePurchase {
+    // 账户大小：购买钱包(32) + 累计购买数量(8)
+    pub const LEN: usize = 32 + 8;
+
+    pub fn new(buyer: Pubkey) -> Self {
+        Self {
+            buyer,
+            purchased: 0,
+        }
+    }
+}
+
+/// 白名单条目（PDA：["allowlist", mint, wallet]）
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct AllowlistEntry {
+    pub mint: Pubkey,   // 铸币账户
+    pub wallet: Pubkey, // 白名单钱包
+    pub allowance: u64, // 剩余可铸造额度（最小单位）
+}
+
+impl AllowlistEntry {
+    // 账户大小：铸币账户(32) + 白名单钱包(32) + 剩余额度(8)
+    pub const LEN: usize = 32 + 32 + 8;
+
+    pub fn new(mint: Pubkey, wallet: Pubkey, allowance: u64) -> Self {
+        Self {
+            mint,
+            wallet,
+            allowance,
+        }
+    }
+}
+
+/// 奖励累加器的精度（每个质押代币的累计奖励乘以该值后存储）
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// 质押池（PDA：["stake_pool", mint]）
+///
+/// 质押与奖励使用同一种代币，奖励由程序铸币权限 PDA 铸造；
+/// 每秒 `reward_rate` 的奖励按质押量比例分配给所有质押者
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct StakePool {
+    pub mint: Pubkey,               // 铸币账户
+    pub reward_rate: u64,           // 每秒释放的奖励总量（最小单位）
+    pub total_staked: u64,          // 质押总量
+    pub acc_reward_per_token: u128, // 每个质押代币的累计奖励（乘以 REWARD_PRECISION）
+    pub last_update_ts: i64,        // 累加器上次更新时间（Unix 时间戳）
+}
+
+impl StakePool {
+    // 账户大小：铸币账户(32) + 奖励速率(8) + 质押总量(8) + 累加器(16) + 上次更新时间(8)
+    pub const LEN: usize = 32 + 8 + 8 + 16 + 8;
+
+    pub fn new(mint: Pubkey, reward_rate: u64, now: i64) -> Self {
+        Self {
+            mint,
+            reward_rate,
+            total_staked: 0,
+            acc_reward_per_token: 0,
+            last_update_ts: now,
+        }
+    }
+
+    /// 将累加器推进到 `now`（无人质押期间的奖励不分配）
+    pub fn update(&mut self, now: i64) -> Result<(), TokenError> {
+        if now <= self.last_update_ts {
+            return Ok(());
+        }
+        if self.total_staked > 0 {
+            let elapsed = (now - self.last_update_ts) as u128;
+            self.acc_reward_per_token = elapsed
+                .checked_mul(self.reward_rate as u128)
+                .and_then(|reward| reward.checked_mul(REWARD_PRECISION))
+                .and_then(|reward| reward.checked_div(self.total_staked as u128))
+                .and_then(|per_token| self.acc_reward_per_token.checked_add(per_token))
+                .ok_or(TokenError::ArithmeticOverflow)?;
+        }
+        self.last_update_ts = now;
+        Ok(())
+    }
+}
+
+/// 用户质押记录（PDA：["stake", pool, owner]）
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct UserStake {
+    pub owner: Pubkey,               // 质押者
+    pub amount: u64,                 // 已质押数量
+    pub reward_per_token_paid: u128, // 上次结算时的累加器值
+    pub pending_rewards: u64,        // 已结算未领取的奖励
+}
+
+impl UserStake {
+    // 账户大小：质押者(32) + 已质押数量(8) + 累加器快照(16) + 待领取奖励(8)
+    pub const LEN: usize = 32 + 8 + 16 + 8;
+
+    pub fn new(owner: Pubkey, acc_reward_per_token: u128) -> Self {
+        Self {
+            owner,
+            amount: 0,
+            reward_per_token_paid: acc_reward_per_token,
+            pending_rewards: 0,
+        }
+    }
+
+    /// 按池子当前累加器结算奖励（须先调用 `StakePool::update`）
+    pub fn settle(&mut self, pool: &StakePool) -> Result<(), TokenError> {
+        let earned = pool
+            .acc_reward_per_token
+            .checked_sub(self.reward_per_token_paid)
+            .and_then(|delta| delta.checked_mul(self.amount as u128))
+            .map(|reward| reward / REWARD_PRECISION)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        let earned = u64::try_from(earned).map_err(|_| TokenError::ArithmeticOverflow)?;
+        self.pending_rewards = self
+            .pending_rewards
+            .checked_add(earned)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        self.reward_per_token_paid = pool.acc_reward_per_token;
+        Ok(())
+    }
+}
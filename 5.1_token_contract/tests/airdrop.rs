@@ -0,0 +1,158 @@
+//! 默克尔空投：CreateDistributor / Claim
+
+mod common;
+
+use common::assert_token_error;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token::{
+    error::TokenError,
+    instruction,
+    merkle::{MerkleTree, leaf_hash, verify},
+};
+
+#[tokio::test]
+async fn claim_once_with_valid_proof() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let alice = test.wallet().await;
+    let bob = test.wallet().await;
+    let claims = [
+        (alice.pubkey(), 100),
+        (bob.pubkey(), 250),
+        (Pubkey::new_unique(), 7),
+    ];
+    let tree = MerkleTree::from_claims(&claims).unwrap();
+    let create = instruction::create_distributor(
+        &test.program_id,
+        &mint,
+        &test.payer().pubkey(),
+        tree.root(),
+        tree.len() as u64,
+    );
+    test.send(&create, &[]).await.unwrap();
+
+    let claim = |claimant: &Keypair, index: u64, amount: u64, proof: Vec<[u8; 32]>| {
+        instruction::claim(
+            &test.program_id,
+            &spl_token::id(),
+            &mint,
+            &claimant.pubkey(),
+            index,
+            amount,
+            proof,
+        )
+    };
+    let alice_claim = claim(&alice, 0, 100, tree.proof(0));
+    let bob_inflated = claim(&bob, 1, 1_000, tree.proof(1));
+    let bob_steals = claim(&bob, 0, 100, tree.proof(0));
+    let out_of_range = claim(&bob, 3, 250, tree.proof(1));
+
+    test.send(&alice_claim, &[&alice]).await.unwrap();
+    assert_eq!(test.balance(&alice.pubkey(), &mint).await, 100);
+
+    // 同一下标只能领取一次
+    assert_token_error(
+        test.send(&alice_claim, &[&alice]).await,
+        TokenError::AlreadyClaimed,
+    );
+
+    // 证明与领取人、数量绑定
+    assert_token_error(
+        test.send(&bob_inflated, &[&bob]).await,
+        TokenError::InvalidMerkleProof,
+    );
+    assert_token_error(
+        test.send(&bob_steals, &[&bob]).await,
+        TokenError::InvalidMerkleProof,
+    );
+    assert_token_error(
+        test.send(&out_of_range, &[&bob]).await,
+        TokenError::ClaimIndexOutOfRange,
+    );
+    assert_eq!(test.balance(&bob.pubkey(), &mint).await, 0);
+    assert_eq!(test.balance(&alice.pubkey(), &mint).await, 100);
+}
+
+#[tokio::test]
+async fn claim_respects_max_supply() {
+    let mut test = common::start().await;
+    let mint = test.create_token(300).await;
+    let alice = test.wallet().await;
+    let bob = test.wallet().await;
+    let claims = [(alice.pubkey(), 200), (bob.pubkey(), 200)];
+    let tree = MerkleTree::from_claims(&claims).unwrap();
+    let create = instruction::create_distributor(
+        &test.program_id,
+        &mint,
+        &test.payer().pubkey(),
+        tree.root(),
+        2,
+    );
+    test.send(&create, &[]).await.unwrap();
+
+    let claim = |claimant: &Keypair, index: usize| {
+        instruction::claim(
+            &test.program_id,
+            &spl_token::id(),
+            &mint,
+            &claimant.pubkey(),
+            index as u64,
+            200,
+            tree.proof(index),
+        )
+    };
+    let alice_claim = claim(&alice, 0);
+    let bob_claim = claim(&bob, 1);
+
+    // 第二笔领取会让发行量超过 300
+    test.send(&alice_claim, &[&alice]).await.unwrap();
+    assert_token_error(
+        test.send(&bob_claim, &[&bob]).await,
+        TokenError::MaxSupplyExceeded,
+    );
+    assert_eq!(test.balance(&alice.pubkey(), &mint).await, 200);
+    assert_eq!(test.balance(&bob.pubkey(), &mint).await, 0);
+}
+
+#[tokio::test]
+async fn only_admin_creates_distributor() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let outsider = test.wallet().await;
+    let tree = MerkleTree::from_claims(&[(outsider.pubkey(), 1_000_000)]).unwrap();
+    let create = instruction::create_distributor(
+        &test.program_id,
+        &mint,
+        &outsider.pubkey(),
+        tree.root(),
+        1,
+    );
+    assert_token_error(
+        test.send(&create, &[&outsider]).await,
+        TokenError::InvalidMintAuthority,
+    );
+}
+
+#[test]
+fn proofs_verify_for_odd_and_even_trees() {
+    assert!(MerkleTree::from_claims(&[]).is_none());
+    for count in 1..=9u64 {
+        let claims: Vec<_> = (0..count).map(|i| (Pubkey::new_unique(), i * 10)).collect();
+        let tree = MerkleTree::from_claims(&claims).unwrap();
+        for (index, (claimant, amount)) in claims.iter().enumerate() {
+            let leaf = leaf_hash(index as u64, claimant, *amount);
+            assert!(
+                verify(&tree.proof(index), &tree.root(), leaf),
+                "{count} leaves, index {index}"
+            );
+            // 错位的证明不能通过
+            if count > 1 {
+                let other = (index + 1) % count as usize;
+                assert!(!verify(&tree.proof(other), &tree.root(), leaf));
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! 铸造白名单：AddToAllowlist / RemoveFromAllowlist 与非管理员铸造
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{TestContext, assert_token_error};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token::{
+    error::TokenError, find_allowlist_address, find_mint_authority_address, instruction,
+    state::AllowlistEntry,
+};
+
+/// 由 `wallet` 自己支付并签名、经程序铸币权限铸造到自己的指令
+fn self_mint(test: &TestContext, mint: &Pubkey, wallet: &Keypair, amount: u64) -> Instruction {
+    instruction::mint(
+        &test.program_id,
+        &spl_token::id(),
+        mint,
+        &wallet.pubkey(),
+        &wallet.pubkey(),
+        &find_mint_authority_address(&test.program_id, mint).0,
+        &[],
+        amount,
+    )
+}
+
+async fn set_allowance(test: &mut TestContext, mint: &Pubkey, wallet: &Pubkey, allowance: u64) {
+    let add = instruction::add_to_allowlist(
+        &test.program_id,
+        mint,
+        &test.payer().pubkey(),
+        wallet,
+        allowance,
+    );
+    test.send(&add, &[]).await.unwrap();
+}
+
+async fn remaining_allowance(test: &mut TestContext, mint: &Pubkey, wallet: &Pubkey) -> u64 {
+    let (entry, _) = find_allowlist_address(&test.program_id, mint, wallet);
+    let account = test
+        .context
+        .banks_client
+        .get_account(entry)
+        .await
+        .unwrap()
+        .expect("allowlist entry exists");
+    AllowlistEntry::try_from_slice(&account.data)
+        .unwrap()
+        .allowance
+}
+
+#[tokio::test]
+async fn allowance_is_consumed_by_each_mint() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let wallet = test.wallet().await;
+    set_allowance(&mut test, &mint, &wallet.pubkey(), 100).await;
+
+    test.send(&self_mint(&test, &mint, &wallet, 60), &[&wallet])
+        .await
+        .unwrap();
+    assert_eq!(
+        remaining_allowance(&mut test, &mint, &wallet.pubkey()).await,
+        40
+    );
+
+    assert_token_error(
+        test.send(&self_mint(&test, &mint, &wallet, 41), &[&wallet])
+            .await,
+        TokenError::AllowanceExceeded,
+    );
+    test.send(&self_mint(&test, &mint, &wallet, 40), &[&wallet])
+        .await
+        .unwrap();
+    assert_token_error(
+        test.send(&self_mint(&test, &mint, &wallet, 1), &[&wallet])
+            .await,
+        TokenError::AllowanceExceeded,
+    );
+    assert_eq!(test.balance(&wallet.pubkey(), &mint).await, 100);
+
+    // 重新加入白名单覆盖剩余额度，而不是累加
+    set_allowance(&mut test, &mint, &wallet.pubkey(), 5).await;
+    assert_eq!(
+        remaining_allowance(&mut test, &mint, &wallet.pubkey()).await,
+        5
+    );
+}
+
+#[tokio::test]
+async fn unlisted_or_removed_wallets_cannot_mint() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let wallet = test.wallet().await;
+
+    assert_token_error(
+        test.send(&self_mint(&test, &mint, &wallet, 1), &[&wallet])
+            .await,
+        TokenError::InvalidAllowlistAccount,
+    );
+
+    set_allowance(&mut test, &mint, &wallet.pubkey(), 10).await;
+    let remove = instruction::remove_from_allowlist(
+        &test.program_id,
+        &mint,
+        &test.payer().pubkey(),
+        &wallet.pubkey(),
+    );
+    test.send(&remove, &[]).await.unwrap();
+
+    let (entry, _) = find_allowlist_address(&test.program_id, &mint, &wallet.pubkey());
+    let closed = test.context.banks_client.get_account(entry).await.unwrap();
+    assert!(closed.is_none());
+    assert_token_error(
+        test.send(&self_mint(&test, &mint, &wallet, 1), &[&wallet])
+            .await,
+        TokenError::InvalidAllowlistAccount,
+    );
+
+    // 管理员不受白名单限制
+    test.mint_to(&mint, &wallet.pubkey(), 1_000).await.unwrap();
+}
+
+#[tokio::test]
+async fn only_admin_manages_allowlist() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let outsider = test.wallet().await;
+
+    let add_self = instruction::add_to_allowlist(
+        &test.program_id,
+        &mint,
+        &outsider.pubkey(),
+        &outsider.pubkey(),
+        u64::MAX,
+    );
+    assert_token_error(
+        test.send(&add_self, &[&outsider]).await,
+        TokenError::InvalidAdmin,
+    );
+}
@@ -0,0 +1,106 @@
+//! 权限变更：SetAuthority 轮换与撤销铸币 / 冻结权限
+
+mod common;
+
+use common::assert_token_error;
+use solana_sdk::signature::{Keypair, Signer};
+use token::{
+    error::TokenError,
+    find_mint_authority_address,
+    instruction::{self, AuthorityType},
+};
+
+#[tokio::test]
+async fn rotate_program_authority_then_revoke() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let (program_authority, _) = find_mint_authority_address(&test.program_id, &mint);
+    let owner = test.wallet().await;
+    let recipient = test.wallet().await;
+
+    // 程序 PDA 权限只能由管理员轮换
+    let hijack = instruction::set_authority(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &program_authority,
+        Some(&owner.pubkey()),
+        &[],
+        AuthorityType::MintTokens,
+        Some(owner.pubkey()),
+    );
+    assert_token_error(
+        test.send(&hijack, &[&owner]).await,
+        TokenError::InvalidAdmin,
+    );
+
+    let rotate = instruction::set_authority(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &program_authority,
+        Some(&test.payer().pubkey()),
+        &[],
+        AuthorityType::MintTokens,
+        Some(owner.pubkey()),
+    );
+    test.send(&rotate, &[]).await.unwrap();
+    assert!(test.mint_to(&mint, &recipient.pubkey(), 1).await.is_err());
+    assert_token_error(
+        test.send(&rotate, &[]).await,
+        TokenError::InvalidMintAuthority,
+    );
+
+    // 新权限自行签名铸造
+    let mint_ix = instruction::mint(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &recipient.pubkey(),
+        &owner.pubkey(),
+        &owner.pubkey(),
+        &[],
+        7,
+    );
+    test.send(&mint_ix, &[&owner]).await.unwrap();
+    assert_eq!(test.balance(&recipient.pubkey(), &mint).await, 7);
+
+    // 撤销后任何人都不能再铸造
+    let revoke = instruction::set_authority(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &owner.pubkey(),
+        None,
+        &[],
+        AuthorityType::MintTokens,
+        None,
+    );
+    test.send(&revoke, &[&owner]).await.unwrap();
+    assert!(test.send(&mint_ix, &[&owner]).await.is_err());
+    assert_token_error(
+        test.send(&revoke, &[&owner]).await,
+        TokenError::InvalidMintAuthority,
+    );
+}
+
+#[tokio::test]
+async fn freeze_authority_must_exist() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let someone = Keypair::new();
+    let set_freeze = instruction::set_authority(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &someone.pubkey(),
+        None,
+        &[],
+        AuthorityType::FreezeAccount,
+        None,
+    );
+    assert_token_error(
+        test.send(&set_freeze, &[&someone]).await,
+        TokenError::InvalidFreezeAuthority,
+    );
+}
@@ -0,0 +1,46 @@
+//! 销毁：Burn
+
+mod common;
+
+use common::assert_token_error;
+use solana_sdk::{program_pack::Pack, signature::Signer};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Mint;
+use token::{error::TokenError, instruction};
+
+#[tokio::test]
+async fn burn_reduces_balance_and_supply() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let holder = test.wallet().await;
+    test.mint_to(&mint, &holder.pubkey(), 50).await.unwrap();
+    let burn = instruction::burn(
+        &test.program_id,
+        &spl_token::id(),
+        &get_associated_token_address(&holder.pubkey(), &mint),
+        &mint,
+        &holder.pubkey(),
+        20,
+    );
+
+    // 代币账户与铸币账户都必须可写
+    for index in [0, 1] {
+        let mut read_only = burn.clone();
+        read_only.accounts[index].is_writable = false;
+        assert_token_error(
+            test.send(&read_only, &[&holder]).await,
+            TokenError::AccountNotWritable,
+        );
+    }
+
+    test.send(&burn, &[&holder]).await.unwrap();
+    assert_eq!(test.balance(&holder.pubkey(), &mint).await, 30);
+    let account = test
+        .context
+        .banks_client
+        .get_account(mint)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(Mint::unpack(&account.data).unwrap().supply, 30);
+}
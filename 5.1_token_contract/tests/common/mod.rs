@@ -0,0 +1,163 @@
+//! 集成测试公共工具
+//!
+//! 在 solana-program-test 中以原生处理器加载本程序（SPL Token、Token-2022 与
+//! 关联令牌账户程序由 program-test 自带），并封装创建代币、铸造、查询余额与调整时钟
+
+#![allow(dead_code)]
+
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext, processor};
+use solana_sdk::{
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use solana_system_interface::instruction as system_instruction;
+use spl_associated_token_account::get_associated_token_address;
+use token::{error::TokenError, find_mint_authority_address, instruction};
+
+/// 测试代币的小数位数
+pub const DECIMALS: u8 = 6;
+
+pub struct TestContext {
+    pub context: ProgramTestContext,
+    pub program_id: Pubkey,
+}
+
+/// 启动加载了本程序的测试验证器
+pub async fn start() -> TestContext {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        "token",
+        program_id,
+        processor!(token::processor::Processor::process),
+    );
+    TestContext {
+        context: program_test.start_with_context().await,
+        program_id,
+    }
+}
+
+impl TestContext {
+    /// 支付账户（同时也是测试代币的管理员）
+    pub fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    /// 以支付账户付费发送单条指令；每次取新的区块哈希，相同指令可以重复提交
+    pub async fn send(
+        &mut self,
+        instruction: &Instruction,
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let payer = self.payer();
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            std::slice::from_ref(instruction),
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// 创建铸币权限为程序 PDA 的 SPL Token 代币，管理员为支付账户
+    pub async fn create_token(&mut self, max_supply: u64) -> Pubkey {
+        let mint = Keypair::new();
+        let (mint_authority, _) = find_mint_authority_address(&self.program_id, &mint.pubkey());
+        let create = instruction::create_token(
+            &self.program_id,
+            &spl_token::id(),
+            &mint.pubkey(),
+            &mint_authority,
+            &self.payer().pubkey(),
+            DECIMALS,
+            None,
+            max_supply,
+            None,
+            "Test Token".to_string(),
+            "TEST".to_string(),
+            String::new(),
+            None,
+        );
+        self.send(&create, &[&mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    /// 由管理员通过程序 PDA 铸造到 `recipient` 的关联令牌账户
+    pub async fn mint_to(
+        &mut self,
+        mint: &Pubkey,
+        recipient: &Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let (mint_authority, _) = find_mint_authority_address(&self.program_id, mint);
+        let mint_ix = instruction::mint(
+            &self.program_id,
+            &spl_token::id(),
+            mint,
+            recipient,
+            &self.payer().pubkey(),
+            &mint_authority,
+            &[],
+            amount,
+        );
+        self.send(&mint_ix, &[]).await
+    }
+
+    /// 给账户转入 SOL，使其可以作为签名者支付租金
+    pub async fn fund(&mut self, account: &Pubkey, lamports: u64) {
+        let transfer = system_instruction::transfer(&self.payer().pubkey(), account, lamports);
+        self.send(&transfer, &[]).await.unwrap();
+    }
+
+    /// 创建并注资一个新钱包
+    pub async fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.fund(&wallet.pubkey(), 1_000_000_000).await;
+        wallet
+    }
+
+    /// `owner` 在 `mint` 下关联令牌账户的余额（账户不存在时为 0）
+    pub async fn balance(&mut self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let ata = get_associated_token_address(owner, mint);
+        match self.context.banks_client.get_account(ata).await.unwrap() {
+            Some(account) => {
+                spl_token::state::Account::unpack(&account.data)
+                    .unwrap()
+                    .amount
+            }
+            None => 0,
+        }
+    }
+
+    /// 当前链上时间
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    /// 把链上时钟调到 `unix_timestamp`
+    pub async fn set_time(&mut self, unix_timestamp: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+}
+
+/// 断言交易因本程序的自定义错误 `expected` 失败
+pub fn assert_token_error(result: Result<(), BanksClientError>, expected: TokenError) {
+    match result.expect_err("transaction should fail").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, expected as u32, "expected {expected:?}")
+        }
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
@@ -0,0 +1,135 @@
+//! 委托转账：Approve / Revoke / TransferFrom
+
+mod common;
+
+use common::{DECIMALS, TestContext};
+use solana_sdk::{
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Account;
+use token::instruction;
+
+struct Allowance {
+    mint: Pubkey,
+    owner: Keypair,
+    spender: Keypair,
+    recipient: Pubkey,
+    source: Pubkey,
+}
+
+impl Allowance {
+    /// owner 持有 100 个代币并授权 spender 转出 `amount`
+    async fn approved(test: &mut TestContext, amount: u64) -> Self {
+        let mint = test.create_token(u64::MAX).await;
+        let owner = test.wallet().await;
+        let spender = test.wallet().await;
+        test.mint_to(&mint, &owner.pubkey(), 100).await.unwrap();
+        let allowance = Self {
+            source: get_associated_token_address(&owner.pubkey(), &mint),
+            recipient: Pubkey::new_unique(),
+            mint,
+            owner,
+            spender,
+        };
+        allowance.approve(test, amount).await;
+        allowance
+    }
+
+    async fn approve(&self, test: &mut TestContext, amount: u64) {
+        let approve = instruction::approve(
+            &test.program_id,
+            &spl_token::id(),
+            &self.source,
+            &self.mint,
+            &self.spender.pubkey(),
+            &self.owner.pubkey(),
+            amount,
+        );
+        test.send(&approve, &[&self.owner]).await.unwrap();
+    }
+
+    /// 由 spender 签名并支付，从 owner 的账户转出 `amount`
+    async fn spend(&self, test: &mut TestContext, amount: u64) -> bool {
+        let transfer = instruction::transfer_from(
+            &test.program_id,
+            &spl_token::id(),
+            &self.source,
+            &self.mint,
+            &self.recipient,
+            &self.spender.pubkey(),
+            &self.spender.pubkey(),
+            amount,
+            DECIMALS,
+        );
+        test.send(&transfer, &[&self.spender]).await.is_ok()
+    }
+
+    async fn source_account(&self, test: &mut TestContext) -> Account {
+        let account = test
+            .context
+            .banks_client
+            .get_account(self.source)
+            .await
+            .unwrap()
+            .unwrap();
+        Account::unpack(&account.data).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn delegate_spends_up_to_allowance() {
+    let mut test = common::start().await;
+    let allowance = Allowance::approved(&mut test, 50).await;
+
+    assert!(allowance.spend(&mut test, 30).await);
+    let source = allowance.source_account(&mut test).await;
+    assert_eq!(source.delegate, COption::Some(allowance.spender.pubkey()));
+    assert_eq!(source.delegated_amount, 20);
+
+    assert!(!allowance.spend(&mut test, 21).await);
+    assert!(allowance.spend(&mut test, 20).await);
+    assert!(!allowance.spend(&mut test, 1).await);
+
+    assert_eq!(
+        test.balance(&allowance.owner.pubkey(), &allowance.mint)
+            .await,
+        50
+    );
+    assert_eq!(
+        test.balance(&allowance.recipient, &allowance.mint).await,
+        50
+    );
+}
+
+#[tokio::test]
+async fn revoke_removes_delegate() {
+    let mut test = common::start().await;
+    let allowance = Allowance::approved(&mut test, 100).await;
+
+    let revoke = instruction::revoke(
+        &test.program_id,
+        &spl_token::id(),
+        &allowance.source,
+        &allowance.owner.pubkey(),
+    );
+    test.send(&revoke, &[&allowance.owner]).await.unwrap();
+
+    let source = allowance.source_account(&mut test).await;
+    assert_eq!(source.delegate, COption::None);
+    assert_eq!(source.delegated_amount, 0);
+    assert!(!allowance.spend(&mut test, 1).await);
+
+    // 重新授权会覆盖之前的额度
+    allowance.approve(&mut test, 5).await;
+    assert!(!allowance.spend(&mut test, 6).await);
+    assert!(allowance.spend(&mut test, 5).await);
+    assert_eq!(
+        test.balance(&allowance.owner.pubkey(), &allowance.mint)
+            .await,
+        95
+    );
+}
@@ -0,0 +1,94 @@
+//! 结构化事件：`Program data:` 日志的编码与还原
+//!
+//! 原生处理器下 `sol_log_data` 不进入交易日志，这里直接构造运行时格式的日志
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use solana_sdk::pubkey::Pubkey;
+use token::event::{TokenEvent, decode_event, parse_logs};
+
+#[test]
+fn discriminators_are_stable() {
+    // 已发布事件的判别符不能改变（新事件只能追加在末尾）
+    let mint = Pubkey::new_unique();
+    let samples = [
+        (
+            0,
+            TokenEvent::TokenCreated {
+                mint,
+                admin: mint,
+                decimals: 0,
+                max_supply: 0,
+            },
+        ),
+        (
+            1,
+            TokenEvent::Minted {
+                mint,
+                destination: mint,
+                amount: 0,
+            },
+        ),
+        (
+            17,
+            TokenEvent::PauseUpdated {
+                mint,
+                paused: false,
+            },
+        ),
+    ];
+    for (discriminator, event) in samples {
+        let data = borsh::to_vec(&event).unwrap();
+        assert_eq!(data[0], discriminator, "{}", event.name());
+        assert_eq!(decode_event(&data), Some(event));
+    }
+}
+
+#[test]
+fn parse_logs_skips_other_programs_and_garbage() {
+    let program_id = Pubkey::new_unique();
+    let other_program = Pubkey::new_unique();
+    let event = TokenEvent::OfferCancelled {
+        offer: Pubkey::new_unique(),
+    };
+    let data = format!(
+        "Program data: {}",
+        STANDARD.encode(borsh::to_vec(&event).unwrap())
+    );
+    let logs = [
+        format!("Program {program_id} invoke [1]"),
+        format!("Program {other_program} invoke [2]"),
+        data.clone(),
+        format!("Program {other_program} success"),
+        "Program data: not-base64".to_string(),
+        format!("Program data: {}", STANDARD.encode([u8::MAX])),
+        data,
+        format!("Program {program_id} success"),
+    ];
+    assert_eq!(parse_logs(&program_id, &logs), [event]);
+    assert_eq!(decode_event(&[]), None);
+}
+
+#[test]
+fn parse_logs_follows_cpi_into_this_program() {
+    // 本程序被其他程序 CPI 调用时输出的事件同样属于本程序；失败的调用也要出栈
+    let program_id = Pubkey::new_unique();
+    let router = Pubkey::new_unique();
+    let event = TokenEvent::Burned {
+        mint: Pubkey::new_unique(),
+        account: Pubkey::new_unique(),
+        amount: 42,
+    };
+    let data = format!(
+        "Program data: {}",
+        STANDARD.encode(borsh::to_vec(&event).unwrap())
+    );
+    let logs = [
+        format!("Program {router} invoke [1]"),
+        format!("Program {program_id} invoke [2]"),
+        data.clone(),
+        format!("Program {program_id} failed: custom program error: 0x0"),
+        data,
+        format!("Program {router} success"),
+    ];
+    assert_eq!(parse_logs(&program_id, &logs), [event]);
+}
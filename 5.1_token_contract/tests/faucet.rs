@@ -0,0 +1,96 @@
+//! 限速水龙头：CreateFaucet / RequestTokens
+
+mod common;
+
+use common::assert_token_error;
+use solana_sdk::{clock::Clock, pubkey::Pubkey, signature::Signer};
+use token::{error::TokenError, instruction};
+
+const AMOUNT_PER_REQUEST: u64 = 50;
+const COOLDOWN_SLOTS: u64 = 100;
+
+#[tokio::test]
+async fn cooldown_is_per_wallet() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let create = instruction::create_faucet(
+        &test.program_id,
+        &mint,
+        &test.payer().pubkey(),
+        AMOUNT_PER_REQUEST,
+        COOLDOWN_SLOTS,
+    );
+    test.send(&create, &[]).await.unwrap();
+
+    let alice = test.wallet().await;
+    let bob = test.wallet().await;
+    let request_for = |wallet: &Pubkey| {
+        instruction::request_tokens(&test.program_id, &spl_token::id(), &mint, wallet)
+    };
+    let alice_request = request_for(&alice.pubkey());
+    let bob_request = request_for(&bob.pubkey());
+
+    test.send(&alice_request, &[&alice]).await.unwrap();
+    assert_eq!(
+        test.balance(&alice.pubkey(), &mint).await,
+        AMOUNT_PER_REQUEST
+    );
+
+    // 冷却期内再次领取被拒绝，但不影响其他钱包
+    assert_token_error(
+        test.send(&alice_request, &[&alice]).await,
+        TokenError::FaucetCooldownActive,
+    );
+    test.send(&bob_request, &[&bob]).await.unwrap();
+    assert_eq!(test.balance(&bob.pubkey(), &mint).await, AMOUNT_PER_REQUEST);
+
+    // 冷却结束后可以再次领取
+    let clock: Clock = test.context.banks_client.get_sysvar().await.unwrap();
+    test.context
+        .warp_to_slot(clock.slot + COOLDOWN_SLOTS)
+        .unwrap();
+    test.send(&alice_request, &[&alice]).await.unwrap();
+    assert_eq!(
+        test.balance(&alice.pubkey(), &mint).await,
+        2 * AMOUNT_PER_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn faucet_respects_max_supply() {
+    let mut test = common::start().await;
+    let mint = test.create_token(AMOUNT_PER_REQUEST + 1).await;
+    let create = instruction::create_faucet(
+        &test.program_id,
+        &mint,
+        &test.payer().pubkey(),
+        AMOUNT_PER_REQUEST,
+        COOLDOWN_SLOTS,
+    );
+    test.send(&create, &[]).await.unwrap();
+
+    let first = test.wallet().await;
+    let second = test.wallet().await;
+    let request =
+        instruction::request_tokens(&test.program_id, &spl_token::id(), &mint, &first.pubkey());
+    test.send(&request, &[&first]).await.unwrap();
+    let request =
+        instruction::request_tokens(&test.program_id, &spl_token::id(), &mint, &second.pubkey());
+    assert_token_error(
+        test.send(&request, &[&second]).await,
+        TokenError::MaxSupplyExceeded,
+    );
+}
+
+#[tokio::test]
+async fn only_admin_creates_faucet() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let outsider = test.wallet().await;
+    let create =
+        instruction::create_faucet(&test.program_id, &mint, &outsider.pubkey(), u64::MAX, 0);
+    assert_token_error(
+        test.send(&create, &[&outsider]).await,
+        TokenError::InvalidMintAuthority,
+    );
+}
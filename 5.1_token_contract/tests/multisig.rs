@@ -0,0 +1,269 @@
+//! 多签铸币权限：CreateToken(multisig_threshold) / Mint / BatchMint / UpdateMetadata / SetAuthority(多签签名者)
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{DECIMALS, TestContext, assert_token_error};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token::{
+    error::TokenError,
+    find_metadata_address, find_mint_authority_address, find_multisig_address,
+    instruction::{self, AuthorityType},
+    state::TokenMetadata,
+};
+
+/// 构建铸币权限为 `m`-of-`signers.len()` 多签的 `CreateToken` 指令
+fn create_multisig_token(
+    test: &TestContext,
+    mint: &Keypair,
+    m: u8,
+    signers: &[Pubkey],
+) -> Instruction {
+    let (multisig, _) = find_multisig_address(&test.program_id, &mint.pubkey());
+    instruction::create_token(
+        &test.program_id,
+        &spl_token::id(),
+        &mint.pubkey(),
+        &multisig,
+        &test.payer().pubkey(),
+        DECIMALS,
+        None,
+        u64::MAX,
+        None,
+        "Multisig Token".to_string(),
+        "MSIG".to_string(),
+        String::new(),
+        Some((m, signers)),
+    )
+}
+
+#[tokio::test]
+async fn mint_requires_threshold_signers() {
+    let mut test = common::start().await;
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let keys: Vec<Pubkey> = signers.iter().map(Signer::pubkey).collect();
+    let mint = Keypair::new();
+    let (multisig, _) = find_multisig_address(&test.program_id, &mint.pubkey());
+    let create = create_multisig_token(&test, &mint, 2, &keys);
+    test.send(&create, &[&mint]).await.unwrap();
+
+    let recipient = test.wallet().await;
+    let mint_with = |approvers: &[Pubkey], amount| {
+        instruction::mint(
+            &test.program_id,
+            &spl_token::id(),
+            &mint.pubkey(),
+            &recipient.pubkey(),
+            &test.payer().pubkey(),
+            &multisig,
+            approvers,
+            amount,
+        )
+    };
+    let one_of_three = mint_with(&keys[..1], 10);
+    let two_of_three = mint_with(&keys[1..], 20);
+
+    // 只有 1 个签名者不足门限
+    assert!(test.send(&one_of_three, &[&signers[0]]).await.is_err());
+    test.send(&two_of_three, &[&signers[1], &signers[2]])
+        .await
+        .unwrap();
+    assert_eq!(test.balance(&recipient.pubkey(), &mint.pubkey()).await, 20);
+}
+
+#[tokio::test]
+async fn rejects_invalid_threshold() {
+    let mut test = common::start().await;
+    let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+    for m in [0, 3] {
+        let mint = Keypair::new();
+        let create = create_multisig_token(&test, &mint, m, &keys);
+        assert_token_error(
+            test.send(&create, &[&mint]).await,
+            TokenError::InvalidMultisig,
+        );
+    }
+
+    // 多签账户必须是 ["multisig", mint] PDA，不能是调用者持有私钥的账户
+    let (mint, impostor) = (Keypair::new(), Keypair::new());
+    let mut create = create_multisig_token(&test, &mint, 1, &keys);
+    create.accounts[1].pubkey = impostor.pubkey();
+    create.accounts[1].is_signer = true;
+    assert_token_error(
+        test.send(&create, &[&mint, &impostor]).await,
+        TokenError::InvalidMultisig,
+    );
+}
+
+#[tokio::test]
+async fn update_metadata_requires_threshold_signers() {
+    let mut test = common::start().await;
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let keys: Vec<Pubkey> = signers.iter().map(Signer::pubkey).collect();
+    let mint = Keypair::new();
+    let (multisig, _) = find_multisig_address(&test.program_id, &mint.pubkey());
+    test.send(&create_multisig_token(&test, &mint, 2, &keys), &[&mint])
+        .await
+        .unwrap();
+
+    let program_id = test.program_id;
+    let update = |approvers: &[Pubkey], name: &str| {
+        instruction::update_metadata(
+            &program_id,
+            &mint.pubkey(),
+            &multisig,
+            approvers,
+            name.to_string(),
+            "MSIG".to_string(),
+            String::new(),
+        )
+    };
+
+    // 重复的签名者只计一次，非多签成员不计数
+    let outsider = Keypair::new();
+    for (approvers, signed_by) in [
+        (vec![keys[0]], vec![&signers[0]]),
+        (vec![keys[0], keys[0]], vec![&signers[0]]),
+        (
+            vec![keys[0], outsider.pubkey()],
+            vec![&signers[0], &outsider],
+        ),
+    ] {
+        assert_token_error(
+            test.send(&update(&approvers, "Hijacked"), &signed_by).await,
+            TokenError::InvalidMintAuthority,
+        );
+    }
+
+    test.send(&update(&keys[..2], "Renamed"), &[&signers[0], &signers[1]])
+        .await
+        .unwrap();
+    let (metadata, _) = find_metadata_address(&test.program_id, &mint.pubkey());
+    let account = test
+        .context
+        .banks_client
+        .get_account(metadata)
+        .await
+        .unwrap()
+        .unwrap();
+    let metadata = TokenMetadata::deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(metadata.name, "Renamed");
+}
+
+#[tokio::test]
+async fn multisig_rotates_mint_authority() {
+    let mut test = common::start().await;
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let keys: Vec<Pubkey> = signers.iter().map(Signer::pubkey).collect();
+    let mint = Keypair::new();
+    let (multisig, _) = find_multisig_address(&test.program_id, &mint.pubkey());
+    test.send(&create_multisig_token(&test, &mint, 2, &keys), &[&mint])
+        .await
+        .unwrap();
+
+    let owner = test.wallet().await;
+    let program_id = test.program_id;
+    let rotate = |approvers: &[Pubkey]| {
+        instruction::set_authority(
+            &program_id,
+            &spl_token::id(),
+            &mint.pubkey(),
+            &multisig,
+            None,
+            approvers,
+            AuthorityType::MintTokens,
+            Some(owner.pubkey()),
+        )
+    };
+    assert!(
+        test.send(&rotate(&keys[..1]), &[&signers[0]])
+            .await
+            .is_err()
+    );
+    test.send(&rotate(&keys[1..]), &[&signers[1], &signers[2]])
+        .await
+        .unwrap();
+
+    // 新权限自行签名铸造，多签不再有效
+    let mint_ix = instruction::mint(
+        &test.program_id,
+        &spl_token::id(),
+        &mint.pubkey(),
+        &owner.pubkey(),
+        &owner.pubkey(),
+        &owner.pubkey(),
+        &[],
+        3,
+    );
+    test.send(&mint_ix, &[&owner]).await.unwrap();
+    assert_eq!(test.balance(&owner.pubkey(), &mint.pubkey()).await, 3);
+    assert_token_error(
+        test.send(&rotate(&keys[1..]), &[&signers[1], &signers[2]])
+            .await,
+        TokenError::InvalidMintAuthority,
+    );
+}
+
+#[tokio::test]
+async fn batch_mint_forwards_multisig_signers() {
+    let mut test = common::start().await;
+    let signers = [Keypair::new(), Keypair::new()];
+    let keys: Vec<Pubkey> = signers.iter().map(Signer::pubkey).collect();
+    let mint = Keypair::new();
+    let (multisig, _) = find_multisig_address(&test.program_id, &mint.pubkey());
+    let create = create_multisig_token(&test, &mint, 2, &keys);
+    test.send(&create, &[&mint]).await.unwrap();
+
+    let recipients = [(Pubkey::new_unique(), 7), (Pubkey::new_unique(), 11)];
+    let program_id = test.program_id;
+    let payer = test.payer().pubkey();
+    let batch_with = |approvers: &[Pubkey]| {
+        instruction::batch_mint(
+            &program_id,
+            &spl_token::id(),
+            &mint.pubkey(),
+            &payer,
+            &multisig,
+            approvers,
+            &recipients,
+        )
+    };
+
+    // 2-of-2 多签只附带一个签名者时整批失败
+    assert!(
+        test.send(&batch_with(&keys[1..]), &[&signers[1]])
+            .await
+            .is_err()
+    );
+    test.send(&batch_with(&keys), &[&signers[0], &signers[1]])
+        .await
+        .unwrap();
+    for (recipient, amount) in recipients {
+        assert_eq!(test.balance(&recipient, &mint.pubkey()).await, amount);
+    }
+
+    // 普通铸币权限不接受账户对之后的多余账户
+    let plain_mint = test.create_token(u64::MAX).await;
+    let (mint_authority, _) = find_mint_authority_address(&program_id, &plain_mint);
+    let extra = Keypair::new();
+    let mut batch = instruction::batch_mint(
+        &program_id,
+        &spl_token::id(),
+        &plain_mint,
+        &payer,
+        &mint_authority,
+        &[],
+        &recipients,
+    );
+    batch
+        .accounts
+        .push(AccountMeta::new_readonly(extra.pubkey(), true));
+    assert_token_error(
+        test.send(&batch, &[&extra]).await,
+        TokenError::BatchLengthMismatch,
+    );
+}
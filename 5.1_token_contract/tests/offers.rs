@@ -0,0 +1,182 @@
+//! 双代币原子交换：MakeOffer / TakeOffer / CancelOffer
+
+mod common;
+
+use common::{TestContext, assert_token_error};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+use token::{error::TokenError, find_offer_address, instruction};
+
+struct Swap {
+    maker: Keypair,
+    taker: Keypair,
+    offered_mint: Pubkey,
+    wanted_mint: Pubkey,
+    offer: Pubkey,
+}
+
+/// maker 持有 1_000 个 A，taker 持有 500 个 B；maker 挂单用 600 个 A 换 300 个 B
+async fn make_offer(test: &mut TestContext) -> Swap {
+    let maker = test.wallet().await;
+    let taker = test.wallet().await;
+    let offered_mint = test.create_token(u64::MAX).await;
+    let wanted_mint = test.create_token(u64::MAX).await;
+    test.mint_to(&offered_mint, &maker.pubkey(), 1_000)
+        .await
+        .unwrap();
+    test.mint_to(&wanted_mint, &taker.pubkey(), 500)
+        .await
+        .unwrap();
+
+    let make = instruction::make_offer(
+        &test.program_id,
+        &spl_token::id(),
+        &maker.pubkey(),
+        &get_associated_token_address(&maker.pubkey(), &offered_mint),
+        &offered_mint,
+        600,
+        &wanted_mint,
+        300,
+    );
+    test.send(&make, &[&maker]).await.unwrap();
+
+    let (offer, _) = find_offer_address(
+        &test.program_id,
+        &maker.pubkey(),
+        &offered_mint,
+        &wanted_mint,
+    );
+    Swap {
+        maker,
+        taker,
+        offered_mint,
+        wanted_mint,
+        offer,
+    }
+}
+
+async fn account_exists(test: &mut TestContext, address: Pubkey) -> bool {
+    test.context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .is_some()
+}
+
+#[tokio::test]
+async fn take_swaps_and_closes_offer() {
+    let mut test = common::start().await;
+    let swap = make_offer(&mut test).await;
+    let vault = get_associated_token_address(&swap.offer, &swap.offered_mint);
+    assert_eq!(test.balance(&swap.offer, &swap.offered_mint).await, 600);
+    assert_eq!(
+        test.balance(&swap.maker.pubkey(), &swap.offered_mint).await,
+        400
+    );
+
+    let take = instruction::take_offer(
+        &test.program_id,
+        &spl_token::id(),
+        &spl_token::id(),
+        &swap.taker.pubkey(),
+        &get_associated_token_address(&swap.taker.pubkey(), &swap.wanted_mint),
+        &swap.maker.pubkey(),
+        &swap.offered_mint,
+        &swap.wanted_mint,
+    );
+    test.send(&take, &[&swap.taker]).await.unwrap();
+
+    assert_eq!(
+        test.balance(&swap.taker.pubkey(), &swap.offered_mint).await,
+        600
+    );
+    assert_eq!(
+        test.balance(&swap.taker.pubkey(), &swap.wanted_mint).await,
+        200
+    );
+    assert_eq!(
+        test.balance(&swap.maker.pubkey(), &swap.wanted_mint).await,
+        300
+    );
+
+    // 报价与金库都已关闭，不能再次成交
+    assert!(!account_exists(&mut test, swap.offer).await);
+    assert!(!account_exists(&mut test, vault).await);
+    assert!(test.send(&take, &[&swap.taker]).await.is_err());
+}
+
+#[tokio::test]
+async fn maker_cancels_and_gets_refund() {
+    let mut test = common::start().await;
+    let swap = make_offer(&mut test).await;
+    let cancel = instruction::cancel_offer(
+        &test.program_id,
+        &spl_token::id(),
+        &swap.maker.pubkey(),
+        &get_associated_token_address(&swap.maker.pubkey(), &swap.offered_mint),
+        &swap.offered_mint,
+        &swap.wanted_mint,
+    );
+    test.send(&cancel, &[&swap.maker]).await.unwrap();
+
+    assert_eq!(
+        test.balance(&swap.maker.pubkey(), &swap.offered_mint).await,
+        1_000
+    );
+    assert!(!account_exists(&mut test, swap.offer).await);
+    assert!(
+        !account_exists(
+            &mut test,
+            get_associated_token_address(&swap.offer, &swap.offered_mint)
+        )
+        .await
+    );
+}
+
+#[tokio::test]
+async fn only_maker_can_cancel() {
+    let mut test = common::start().await;
+    let swap = make_offer(&mut test).await;
+
+    // taker 冒充 maker：报价 PDA 由签名者推导，与传入的报价账户不一致
+    let mut cancel = instruction::cancel_offer(
+        &test.program_id,
+        &spl_token::id(),
+        &swap.maker.pubkey(),
+        &get_associated_token_address(&swap.taker.pubkey(), &swap.offered_mint),
+        &swap.offered_mint,
+        &swap.wanted_mint,
+    );
+    cancel.accounts[0].pubkey = swap.taker.pubkey();
+    assert_token_error(
+        test.send(&cancel, &[&swap.taker]).await,
+        TokenError::InvalidOfferAccount,
+    );
+    assert_eq!(test.balance(&swap.offer, &swap.offered_mint).await, 600);
+}
+
+#[tokio::test]
+async fn rejects_same_mint_offer() {
+    let mut test = common::start().await;
+    let maker = test.wallet().await;
+    let mint = test.create_token(u64::MAX).await;
+    test.mint_to(&mint, &maker.pubkey(), 10).await.unwrap();
+    let make = instruction::make_offer(
+        &test.program_id,
+        &spl_token::id(),
+        &maker.pubkey(),
+        &get_associated_token_address(&maker.pubkey(), &mint),
+        &mint,
+        10,
+        &mint,
+        10,
+    );
+    assert_token_error(
+        test.send(&make, &[&maker]).await,
+        TokenError::InvalidOfferTerms,
+    );
+}
@@ -0,0 +1,203 @@
+//! 管理员暂停：Pause / Unpause
+
+mod common;
+
+use common::{DECIMALS, assert_token_error};
+use solana_sdk::signature::{Keypair, Signer};
+use spl_associated_token_account::get_associated_token_address;
+use token::{error::TokenError, instruction};
+
+#[tokio::test]
+async fn only_admin_can_pause_and_unpause() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let outsider = test.wallet().await;
+
+    for build in [instruction::pause, instruction::unpause] {
+        let ix = build(&test.program_id, &mint, &outsider.pubkey());
+        assert_token_error(test.send(&ix, &[&outsider]).await, TokenError::InvalidAdmin);
+    }
+
+    // 管理员可以暂停
+    let admin = test.payer().pubkey();
+    test.send(&instruction::pause(&test.program_id, &mint, &admin), &[])
+        .await
+        .unwrap();
+    assert_token_error(
+        test.mint_to(&mint, &admin, 1).await,
+        TokenError::TokenPaused,
+    );
+}
+
+#[tokio::test]
+async fn pause_blocks_mint_transfer_and_burn_until_unpaused() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let holder = test.wallet().await;
+    let recipient = test.wallet().await;
+    test.mint_to(&mint, &holder.pubkey(), 100).await.unwrap();
+    let admin = test.payer().pubkey();
+    let holder_ata = get_associated_token_address(&holder.pubkey(), &mint);
+
+    let transfer = instruction::transfer(
+        &test.program_id,
+        &spl_token::id(),
+        &holder_ata,
+        &mint,
+        &recipient.pubkey(),
+        &holder.pubkey(),
+        &holder.pubkey(),
+        10,
+        DECIMALS,
+    );
+    let burn = instruction::burn(
+        &test.program_id,
+        &spl_token::id(),
+        &holder_ata,
+        &mint,
+        &holder.pubkey(),
+        10,
+    );
+
+    test.send(&instruction::pause(&test.program_id, &mint, &admin), &[])
+        .await
+        .unwrap();
+    assert_token_error(
+        test.mint_to(&mint, &holder.pubkey(), 1).await,
+        TokenError::TokenPaused,
+    );
+    assert_token_error(
+        test.send(&transfer, &[&holder]).await,
+        TokenError::TokenPaused,
+    );
+    assert_token_error(test.send(&burn, &[&holder]).await, TokenError::TokenPaused);
+    assert_eq!(test.balance(&holder.pubkey(), &mint).await, 100);
+
+    test.send(&instruction::unpause(&test.program_id, &mint, &admin), &[])
+        .await
+        .unwrap();
+    test.send(&transfer, &[&holder]).await.unwrap();
+    test.send(&burn, &[&holder]).await.unwrap();
+    test.mint_to(&mint, &holder.pubkey(), 1).await.unwrap();
+    assert_eq!(test.balance(&holder.pubkey(), &mint).await, 81);
+    assert_eq!(test.balance(&recipient.pubkey(), &mint).await, 10);
+}
+
+#[tokio::test]
+async fn pause_blocks_new_vesting_and_offers() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let wanted_mint = test.create_token(u64::MAX).await;
+    let holder = test.wallet().await;
+    test.mint_to(&mint, &holder.pubkey(), 100).await.unwrap();
+    let holder_ata = get_associated_token_address(&holder.pubkey(), &mint);
+    let admin = test.payer().pubkey();
+
+    let vesting = instruction::create_vesting(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &holder.pubkey(),
+        &holder_ata,
+        &Keypair::new().pubkey(),
+        40,
+        0,
+        0,
+        1_000,
+    );
+    let offer = instruction::make_offer(
+        &test.program_id,
+        &spl_token::id(),
+        &holder.pubkey(),
+        &holder_ata,
+        &mint,
+        60,
+        &wanted_mint,
+        1,
+    );
+
+    // 暂停期间不能把代币锁入新的托管
+    test.send(&instruction::pause(&test.program_id, &mint, &admin), &[])
+        .await
+        .unwrap();
+    assert_token_error(
+        test.send(&vesting, &[&holder]).await,
+        TokenError::TokenPaused,
+    );
+    assert_token_error(test.send(&offer, &[&holder]).await, TokenError::TokenPaused);
+
+    test.send(&instruction::unpause(&test.program_id, &mint, &admin), &[])
+        .await
+        .unwrap();
+    test.send(&vesting, &[&holder]).await.unwrap();
+    test.send(&offer, &[&holder]).await.unwrap();
+    assert_eq!(test.balance(&holder.pubkey(), &mint).await, 0);
+}
+
+#[tokio::test]
+async fn pause_still_lets_users_withdraw_locked_tokens() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let wanted_mint = test.create_token(u64::MAX).await;
+    let holder = test.wallet().await;
+    test.mint_to(&mint, &holder.pubkey(), 100).await.unwrap();
+    let holder_ata = get_associated_token_address(&holder.pubkey(), &mint);
+    let admin = test.payer().pubkey();
+
+    // 暂停前挂出报价并质押
+    let make = instruction::make_offer(
+        &test.program_id,
+        &spl_token::id(),
+        &holder.pubkey(),
+        &holder_ata,
+        &mint,
+        30,
+        &wanted_mint,
+        1,
+    );
+    test.send(&make, &[&holder]).await.unwrap();
+    let init = instruction::init_pool(&test.program_id, &spl_token::id(), &mint, &admin, 1);
+    test.send(&init, &[]).await.unwrap();
+    let program_id = test.program_id;
+    let stake = |amount| {
+        instruction::stake(
+            &program_id,
+            &spl_token::id(),
+            &mint,
+            &holder.pubkey(),
+            &holder_ata,
+            amount,
+        )
+    };
+    test.send(&stake(70), &[&holder]).await.unwrap();
+    assert_eq!(test.balance(&holder.pubkey(), &mint).await, 0);
+
+    test.send(&instruction::pause(&test.program_id, &mint, &admin), &[])
+        .await
+        .unwrap();
+    let cancel = instruction::cancel_offer(
+        &test.program_id,
+        &spl_token::id(),
+        &holder.pubkey(),
+        &holder_ata,
+        &mint,
+        &wanted_mint,
+    );
+    let unstake = instruction::unstake(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &holder.pubkey(),
+        &holder_ata,
+        70,
+    );
+    test.send(&cancel, &[&holder]).await.unwrap();
+    test.send(&unstake, &[&holder]).await.unwrap();
+    assert_eq!(test.balance(&holder.pubkey(), &mint).await, 100);
+
+    // 重新锁定仍然被拒绝
+    assert_token_error(
+        test.send(&stake(1), &[&holder]).await,
+        TokenError::TokenPaused,
+    );
+}
@@ -0,0 +1,223 @@
+//! 固定价格公开发售：CreateSale / Buy
+
+mod common;
+
+use common::{TestContext, assert_token_error};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use token::{error::TokenError, instruction, state::SaleConfig};
+
+/// 每个完整代币（10^6 最小单位）的价格
+const PRICE: u64 = 1_000_000;
+
+async fn lamports(test: &mut TestContext, address: &Pubkey) -> u64 {
+    test.context
+        .banks_client
+        .get_balance(*address)
+        .await
+        .unwrap()
+}
+
+/// 创建代币与发售，返回 (铸币账户, 国库)
+async fn open_sale(
+    test: &mut TestContext,
+    max_supply: u64,
+    wallet_cap: Option<u64>,
+) -> (Pubkey, Keypair) {
+    let mint = test.create_token(max_supply).await;
+    let treasury = test.wallet().await;
+    let create = instruction::create_sale(
+        &test.program_id,
+        &mint,
+        &test.payer().pubkey(),
+        PRICE,
+        &treasury.pubkey(),
+        wallet_cap,
+    );
+    test.send(&create, &[]).await.unwrap();
+    (mint, treasury)
+}
+
+#[tokio::test]
+async fn buyer_pays_treasury_and_receives_tokens() {
+    let mut test = common::start().await;
+    let (mint, treasury) = open_sale(&mut test, u64::MAX, None).await;
+    let buyer = test.wallet().await;
+    let before = lamports(&mut test, &treasury.pubkey()).await;
+
+    // 2.5 个代币
+    let buy = instruction::buy(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &buyer.pubkey(),
+        &treasury.pubkey(),
+        2_500_000,
+    );
+    test.send(&buy, &[&buyer]).await.unwrap();
+
+    assert_eq!(test.balance(&buyer.pubkey(), &mint).await, 2_500_000);
+    assert_eq!(
+        lamports(&mut test, &treasury.pubkey()).await - before,
+        2_500_000
+    );
+}
+
+#[tokio::test]
+async fn wallet_cap_is_cumulative() {
+    let mut test = common::start().await;
+    let (mint, treasury) = open_sale(&mut test, u64::MAX, Some(3_000_000)).await;
+    let buyer = test.wallet().await;
+    let other = test.wallet().await;
+    let buy = |wallet: &Keypair, amount| {
+        instruction::buy(
+            &test.program_id,
+            &spl_token::id(),
+            &mint,
+            &wallet.pubkey(),
+            &treasury.pubkey(),
+            amount,
+        )
+    };
+    let (first, too_much, rest) = (
+        buy(&buyer, 2_000_000),
+        buy(&buyer, 1_000_001),
+        buy(&buyer, 1_000_000),
+    );
+    let other_buy = buy(&other, 3_000_000);
+
+    test.send(&first, &[&buyer]).await.unwrap();
+    assert_token_error(
+        test.send(&too_much, &[&buyer]).await,
+        TokenError::WalletCapExceeded,
+    );
+    test.send(&rest, &[&buyer]).await.unwrap();
+    assert_token_error(
+        test.send(&rest, &[&buyer]).await,
+        TokenError::WalletCapExceeded,
+    );
+    // 上限按钱包计算
+    test.send(&other_buy, &[&other]).await.unwrap();
+    assert_eq!(test.balance(&buyer.pubkey(), &mint).await, 3_000_000);
+    assert_eq!(test.balance(&other.pubkey(), &mint).await, 3_000_000);
+}
+
+#[tokio::test]
+async fn buy_respects_max_supply_and_treasury() {
+    let mut test = common::start().await;
+    let (mint, treasury) = open_sale(&mut test, 1_000_000, None).await;
+    let buyer = test.wallet().await;
+
+    let over_cap = instruction::buy(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &buyer.pubkey(),
+        &treasury.pubkey(),
+        1_000_001,
+    );
+    assert_token_error(
+        test.send(&over_cap, &[&buyer]).await,
+        TokenError::MaxSupplyExceeded,
+    );
+
+    // 把钱付给自己
+    let wrong_treasury = instruction::buy(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &buyer.pubkey(),
+        &buyer.pubkey(),
+        1,
+    );
+    assert_token_error(
+        test.send(&wrong_treasury, &[&buyer]).await,
+        TokenError::InvalidTreasury,
+    );
+    assert_eq!(test.balance(&buyer.pubkey(), &mint).await, 0);
+}
+
+#[tokio::test]
+async fn only_admin_creates_sale() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let outsider = test.wallet().await;
+    let create = instruction::create_sale(
+        &test.program_id,
+        &mint,
+        &outsider.pubkey(),
+        1,
+        &outsider.pubkey(),
+        None,
+    );
+    assert_token_error(
+        test.send(&create, &[&outsider]).await,
+        TokenError::InvalidMintAuthority,
+    );
+}
+
+#[tokio::test]
+async fn sale_needs_price_and_treasury() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let admin = test.payer().pubkey();
+    for (price, treasury) in [(0, Pubkey::new_unique()), (PRICE, Pubkey::default())] {
+        let create =
+            instruction::create_sale(&test.program_id, &mint, &admin, price, &treasury, None);
+        assert_token_error(test.send(&create, &[]).await, TokenError::InvalidSaleTerms);
+    }
+}
+
+#[tokio::test]
+async fn overpriced_purchase_is_an_overflow() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let treasury = Pubkey::new_unique();
+    let create = instruction::create_sale(
+        &test.program_id,
+        &mint,
+        &test.payer().pubkey(),
+        u64::MAX,
+        &treasury,
+        None,
+    );
+    test.send(&create, &[]).await.unwrap();
+
+    // 10 个完整代币的价格超过 u64
+    let buyer = test.wallet().await;
+    let buy = instruction::buy(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &buyer.pubkey(),
+        &treasury,
+        10_000_000,
+    );
+    assert_token_error(
+        test.send(&buy, &[&buyer]).await,
+        TokenError::ArithmeticOverflow,
+    );
+}
+
+#[test]
+fn partial_tokens_round_up() {
+    let sale = SaleConfig::new(Pubkey::new_unique(), 1_000, Pubkey::new_unique(), None);
+    // (最小单位数量, 小数位数, 应付 lamports)
+    let cases = [
+        (1, 6, 1),
+        (999, 6, 1),
+        (1_000, 6, 1),
+        (1_001, 6, 2),
+        (3, 0, 3_000),
+    ];
+    for (amount, decimals, expected) in cases {
+        assert_eq!(
+            sale.cost(amount, decimals),
+            Some(expected),
+            "{amount} @ {decimals}"
+        );
+    }
+    assert_eq!(sale.cost(u64::MAX, 0), None);
+}
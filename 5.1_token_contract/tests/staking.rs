@@ -0,0 +1,225 @@
+//! 质押奖励：InitPool / Stake / Unstake / ClaimRewards
+
+mod common;
+
+use common::{TestContext, assert_token_error};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+use token::{
+    error::TokenError,
+    instruction,
+    state::{StakePool, UserStake},
+};
+
+/// 每秒释放的奖励
+const REWARD_RATE: u64 = 10;
+const T0: i64 = 1_700_000_000;
+
+fn stake(test: &TestContext, mint: &Pubkey, owner: &Keypair, amount: u64) -> Instruction {
+    instruction::stake(
+        &test.program_id,
+        &spl_token::id(),
+        mint,
+        &owner.pubkey(),
+        &get_associated_token_address(&owner.pubkey(), mint),
+        amount,
+    )
+}
+
+fn unstake(test: &TestContext, mint: &Pubkey, owner: &Keypair, amount: u64) -> Instruction {
+    instruction::unstake(
+        &test.program_id,
+        &spl_token::id(),
+        mint,
+        &owner.pubkey(),
+        &get_associated_token_address(&owner.pubkey(), mint),
+        amount,
+    )
+}
+
+fn claim(test: &TestContext, mint: &Pubkey, owner: &Keypair) -> Instruction {
+    instruction::claim_rewards(&test.program_id, &spl_token::id(), mint, &owner.pubkey())
+}
+
+/// 在 T0 创建每秒奖励 `reward_rate` 的代币与质押池，并给每个钱包铸造 `amount`
+async fn pool_with_stakers(
+    test: &mut TestContext,
+    reward_rate: u64,
+    amount: u64,
+    count: usize,
+) -> (Pubkey, Vec<Keypair>) {
+    test.set_time(T0).await;
+    let mint = test.create_token(u64::MAX).await;
+    let init = instruction::init_pool(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &test.payer().pubkey(),
+        reward_rate,
+    );
+    test.send(&init, &[]).await.unwrap();
+
+    let mut stakers = Vec::with_capacity(count);
+    for _ in 0..count {
+        let staker = test.wallet().await;
+        test.mint_to(&mint, &staker.pubkey(), amount).await.unwrap();
+        stakers.push(staker);
+    }
+    (mint, stakers)
+}
+
+#[tokio::test]
+async fn rewards_split_pro_rata_and_survive_unstake() {
+    let mut test = common::start().await;
+    let (mint, stakers) = pool_with_stakers(&mut test, REWARD_RATE, 1_000, 2).await;
+    let [alice, bob] = &stakers[..] else {
+        unreachable!()
+    };
+
+    test.send(&stake(&test, &mint, alice, 300), &[alice])
+        .await
+        .unwrap();
+    test.send(&stake(&test, &mint, bob, 100), &[bob])
+        .await
+        .unwrap();
+    assert_eq!(test.balance(&alice.pubkey(), &mint).await, 700);
+
+    // 100 秒共释放 1_000，按 3:1 分配；Alice 先全部解除质押，奖励仍然保留
+    test.set_time(T0 + 100).await;
+    test.send(&unstake(&test, &mint, alice, 300), &[alice])
+        .await
+        .unwrap();
+    assert_eq!(test.balance(&alice.pubkey(), &mint).await, 1_000);
+    test.send(&claim(&test, &mint, alice), &[alice])
+        .await
+        .unwrap();
+    assert_eq!(test.balance(&alice.pubkey(), &mint).await, 1_750);
+
+    // 之后的奖励全部归 Bob
+    test.set_time(T0 + 200).await;
+    test.send(&claim(&test, &mint, bob), &[bob]).await.unwrap();
+    assert_eq!(test.balance(&bob.pubkey(), &mint).await, 900 + 250 + 1_000);
+    assert_token_error(
+        test.send(&claim(&test, &mint, alice), &[alice]).await,
+        TokenError::NothingToClaim,
+    );
+}
+
+#[tokio::test]
+async fn cannot_unstake_more_than_staked() {
+    let mut test = common::start().await;
+    let (mint, stakers) = pool_with_stakers(&mut test, REWARD_RATE, 500, 1).await;
+    let owner = &stakers[0];
+
+    test.send(&stake(&test, &mint, owner, 200), &[owner])
+        .await
+        .unwrap();
+    assert_token_error(
+        test.send(&unstake(&test, &mint, owner, 201), &[owner])
+            .await,
+        TokenError::InsufficientStake,
+    );
+    test.send(&unstake(&test, &mint, owner, 150), &[owner])
+        .await
+        .unwrap();
+    test.send(&unstake(&test, &mint, owner, 50), &[owner])
+        .await
+        .unwrap();
+    assert_token_error(
+        test.send(&unstake(&test, &mint, owner, 1), &[owner]).await,
+        TokenError::InsufficientStake,
+    );
+    assert_eq!(test.balance(&owner.pubkey(), &mint).await, 500);
+}
+
+#[tokio::test]
+async fn unstake_returns_principal_when_rewards_overflow() {
+    let mut test = common::start().await;
+    let (mint, stakers) = pool_with_stakers(&mut test, u64::MAX, 10, 1).await;
+    let owner = &stakers[0];
+    test.send(&stake(&test, &mint, owner, 1), &[owner])
+        .await
+        .unwrap();
+
+    // 2 秒后待领取奖励超过 u64：领取失败，但解除质押放弃奖励并取回本金
+    test.set_time(T0 + 2).await;
+    assert_token_error(
+        test.send(&claim(&test, &mint, owner), &[owner]).await,
+        TokenError::ArithmeticOverflow,
+    );
+    test.send(&unstake(&test, &mint, owner, 1), &[owner])
+        .await
+        .unwrap();
+    assert_eq!(test.balance(&owner.pubkey(), &mint).await, 10);
+    assert_token_error(
+        test.send(&claim(&test, &mint, owner), &[owner]).await,
+        TokenError::NothingToClaim,
+    );
+}
+
+#[tokio::test]
+async fn only_admin_creates_pool() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let outsider = test.wallet().await;
+    let init = instruction::init_pool(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &outsider.pubkey(),
+        u64::MAX,
+    );
+    assert_token_error(
+        test.send(&init, &[&outsider]).await,
+        TokenError::InvalidMintAuthority,
+    );
+}
+
+#[test]
+fn late_staker_only_earns_from_join() {
+    let mut pool = StakePool::new(Pubkey::new_unique(), 6, 0);
+    let mut early = UserStake::new(Pubkey::new_unique(), pool.acc_reward_per_token);
+    early.amount = 100;
+    pool.total_staked = 100;
+
+    // 前 10 秒只有 early：60
+    pool.update(10).unwrap();
+    let mut late = UserStake::new(Pubkey::new_unique(), pool.acc_reward_per_token);
+    late.amount = 200;
+    pool.total_staked = 300;
+
+    // 后 10 秒按 1:2 分配：20 / 40
+    pool.update(20).unwrap();
+    early.settle(&pool).unwrap();
+    late.settle(&pool).unwrap();
+    assert_eq!(early.pending_rewards, 80);
+    assert_eq!(late.pending_rewards, 40);
+
+    // 时钟回拨不改变累加器
+    let acc = pool.acc_reward_per_token;
+    pool.update(15).unwrap();
+    assert_eq!(pool.acc_reward_per_token, acc);
+}
+
+#[test]
+fn reward_overflow_is_an_error() {
+    let mut pool = StakePool::new(Pubkey::new_unique(), u64::MAX, 0);
+    pool.total_staked = 1;
+    assert_eq!(pool.update(i64::MAX), Err(TokenError::ArithmeticOverflow));
+    // 失败时不修改状态
+    assert_eq!(pool.acc_reward_per_token, 0);
+    assert_eq!(pool.last_update_ts, 0);
+
+    // 单个用户的奖励超过 u64 时报错而不是截断
+    pool.update(1_000).unwrap();
+    let mut whale = UserStake::new(Pubkey::new_unique(), 0);
+    whale.amount = u64::MAX;
+    assert_eq!(whale.settle(&pool), Err(TokenError::ArithmeticOverflow));
+    let mut minnow = UserStake::new(Pubkey::new_unique(), 0);
+    minnow.amount = 1;
+    assert_eq!(minnow.settle(&pool), Err(TokenError::ArithmeticOverflow));
+}
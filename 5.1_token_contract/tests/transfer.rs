@@ -0,0 +1,63 @@
+//! 转账：Transfer（目标 ATA 不存在时由支付账户创建）
+
+mod common;
+
+use common::{DECIMALS, assert_token_error};
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use spl_associated_token_account::get_associated_token_address;
+use token::{error::TokenError, instruction};
+
+#[tokio::test]
+async fn transfer_creates_destination_account() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let sender = test.wallet().await;
+    let recipient = Pubkey::new_unique();
+    test.mint_to(&mint, &sender.pubkey(), 100).await.unwrap();
+
+    let transfer = |amount| {
+        instruction::transfer(
+            &test.program_id,
+            &spl_token::id(),
+            &get_associated_token_address(&sender.pubkey(), &mint),
+            &mint,
+            &recipient,
+            &sender.pubkey(),
+            &sender.pubkey(),
+            amount,
+            DECIMALS,
+        )
+    };
+    let (first, second) = (transfer(40), transfer(60));
+    test.send(&first, &[&sender]).await.unwrap();
+    test.send(&second, &[&sender]).await.unwrap();
+    assert_eq!(test.balance(&sender.pubkey(), &mint).await, 0);
+    assert_eq!(test.balance(&recipient, &mint).await, 100);
+}
+
+#[tokio::test]
+async fn payer_must_be_writable() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let sender = test.wallet().await;
+    let payer = test.wallet().await;
+    test.mint_to(&mint, &sender.pubkey(), 10).await.unwrap();
+
+    let mut transfer = instruction::transfer(
+        &test.program_id,
+        &spl_token::id(),
+        &get_associated_token_address(&sender.pubkey(), &mint),
+        &mint,
+        &Pubkey::new_unique(),
+        &sender.pubkey(),
+        &payer.pubkey(),
+        10,
+        DECIMALS,
+    );
+    // 支付账户（第 6 个账户）标记为只读
+    transfer.accounts[5].is_writable = false;
+    assert_token_error(
+        test.send(&transfer, &[&sender, &payer]).await,
+        TokenError::AccountNotWritable,
+    );
+}
@@ -0,0 +1,168 @@
+//! 线性归属：CreateVesting / ClaimVested
+
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{TestContext, assert_token_error};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use spl_associated_token_account::get_associated_token_address;
+use token::{error::TokenError, find_vesting_address, instruction, state::VestingSchedule};
+
+/// 创建代币并给支付账户铸造 `total`，再为 `beneficiary` 锁入一个归属计划
+async fn setup_vesting(
+    test: &mut TestContext,
+    beneficiary: &Pubkey,
+    total: u64,
+    start_ts: i64,
+    cliff_ts: i64,
+    end_ts: i64,
+) -> Pubkey {
+    let mint = test.create_token(u64::MAX).await;
+    let funder = test.payer();
+    test.mint_to(&mint, &funder.pubkey(), total).await.unwrap();
+    let create = instruction::create_vesting(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &funder.pubkey(),
+        &get_associated_token_address(&funder.pubkey(), &mint),
+        beneficiary,
+        total,
+        start_ts,
+        cliff_ts,
+        end_ts,
+    );
+    test.send(&create, &[]).await.unwrap();
+    mint
+}
+
+#[tokio::test]
+async fn claim_follows_linear_schedule() {
+    let mut test = common::start().await;
+    let beneficiary = test.wallet().await;
+    let start = test.now().await;
+    let mint = setup_vesting(
+        &mut test,
+        &beneficiary.pubkey(),
+        1_000,
+        start,
+        start + 100,
+        start + 1_000,
+    )
+    .await;
+
+    // 全部锁入托管账户
+    let (vesting, _) = find_vesting_address(&test.program_id, &mint, &beneficiary.pubkey());
+    assert_eq!(test.balance(&vesting, &mint).await, 1_000);
+    let claim = instruction::claim_vested(
+        &test.program_id,
+        &spl_token::id(),
+        &mint,
+        &beneficiary.pubkey(),
+    );
+
+    // 悬崖之前没有可领取的代币
+    test.set_time(start + 99).await;
+    assert_token_error(
+        test.send(&claim, &[&beneficiary]).await,
+        TokenError::NothingToClaim,
+    );
+
+    // 过了悬崖按线性进度领取
+    test.set_time(start + 250).await;
+    test.send(&claim, &[&beneficiary]).await.unwrap();
+    assert_eq!(test.balance(&beneficiary.pubkey(), &mint).await, 250);
+
+    // 同一时刻再次领取不会重复发放
+    assert_token_error(
+        test.send(&claim, &[&beneficiary]).await,
+        TokenError::NothingToClaim,
+    );
+
+    // 结束后领取剩余部分
+    test.set_time(start + 5_000).await;
+    test.send(&claim, &[&beneficiary]).await.unwrap();
+    assert_eq!(test.balance(&beneficiary.pubkey(), &mint).await, 1_000);
+    assert_eq!(test.balance(&vesting, &mint).await, 0);
+    assert_token_error(
+        test.send(&claim, &[&beneficiary]).await,
+        TokenError::NothingToClaim,
+    );
+
+    // 归属计划记录的已领取数量与实际发放一致
+    let account = test
+        .context
+        .banks_client
+        .get_account(vesting)
+        .await
+        .unwrap()
+        .unwrap();
+    let schedule = VestingSchedule::try_from_slice(&account.data).unwrap();
+    assert_eq!(schedule.claimed, schedule.total);
+}
+
+#[tokio::test]
+async fn only_beneficiary_can_claim() {
+    let mut test = common::start().await;
+    let beneficiary = Pubkey::new_unique();
+    let start = test.now().await;
+    let mint = setup_vesting(&mut test, &beneficiary, 500, start, start, start + 10).await;
+    test.set_time(start + 10).await;
+
+    // 换成其他签名者领取受益人的计划：归属计划 PDA 与签名者不匹配
+    let thief = test.wallet().await;
+    let mut claim =
+        instruction::claim_vested(&test.program_id, &spl_token::id(), &mint, &beneficiary);
+    claim.accounts[0].pubkey = thief.pubkey();
+    claim.accounts[1].pubkey = get_associated_token_address(&thief.pubkey(), &mint);
+    assert_token_error(
+        test.send(&claim, &[&thief]).await,
+        TokenError::InvalidVestingAccount,
+    );
+    assert_eq!(test.balance(&thief.pubkey(), &mint).await, 0);
+}
+
+#[tokio::test]
+async fn rejects_invalid_schedule() {
+    let mut test = common::start().await;
+    let mint = test.create_token(u64::MAX).await;
+    let funder = test.payer();
+    test.mint_to(&mint, &funder.pubkey(), 100).await.unwrap();
+    let funder_ata = get_associated_token_address(&funder.pubkey(), &mint);
+
+    // (total, start, cliff, end)：总量为 0、悬崖早于开始、结束早于悬崖、区间为空
+    for (total, start_ts, cliff_ts, end_ts) in [
+        (0, 0, 0, 10),
+        (100, 10, 5, 20),
+        (100, 0, 20, 10),
+        (100, 5, 5, 5),
+    ] {
+        let create = instruction::create_vesting(
+            &test.program_id,
+            &spl_token::id(),
+            &mint,
+            &funder.pubkey(),
+            &funder_ata,
+            &Keypair::new().pubkey(),
+            total,
+            start_ts,
+            cliff_ts,
+            end_ts,
+        );
+        assert_token_error(
+            test.send(&create, &[]).await,
+            TokenError::InvalidVestingSchedule,
+        );
+    }
+    assert_eq!(test.balance(&funder.pubkey(), &mint).await, 100);
+}
+
+#[test]
+fn vested_amount_rounds_down() {
+    let schedule = VestingSchedule::new(Pubkey::new_unique(), Pubkey::new_unique(), 10, 0, 0, 3);
+    let vested: Vec<u64> = (0..=4).map(|now| schedule.vested_amount(now)).collect();
+    assert_eq!(vested, [0, 3, 6, 10, 10]);
+}
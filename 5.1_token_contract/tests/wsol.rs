@@ -0,0 +1,40 @@
+//! 原生 SOL 包装：WrapSol / UnwrapSol
+
+mod common;
+
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Signer};
+use spl_associated_token_account::get_associated_token_address;
+use token::{instruction, native_mint_address};
+
+#[tokio::test]
+async fn wrap_twice_then_unwrap_everything() {
+    let mut test = common::start().await;
+    let owner = test.wallet().await;
+    let native_mint = native_mint_address(&spl_token::id());
+    let wsol_account = get_associated_token_address(&owner.pubkey(), &native_mint);
+    let banks = test.context.banks_client.clone();
+    let lamports_before = banks.get_balance(owner.pubkey()).await.unwrap();
+
+    // 第一次包装创建 ATA，第二次在已有账户上同步余额
+    let wrap = instruction::wrap_sol(
+        &test.program_id,
+        &spl_token::id(),
+        &owner.pubkey(),
+        LAMPORTS_PER_SOL / 4,
+    );
+    test.send(&wrap, &[&owner]).await.unwrap();
+    test.send(&wrap, &[&owner]).await.unwrap();
+    assert_eq!(
+        test.balance(&owner.pubkey(), &native_mint).await,
+        LAMPORTS_PER_SOL / 2
+    );
+
+    // 解包关闭账户，包装的 SOL 与 ATA 租金全部退回（交易费由测试支付账户承担）
+    let unwrap = instruction::unwrap_sol(&test.program_id, &spl_token::id(), &owner.pubkey());
+    test.send(&unwrap, &[&owner]).await.unwrap();
+    assert!(banks.get_account(wsol_account).await.unwrap().is_none());
+    assert_eq!(
+        banks.get_balance(owner.pubkey()).await.unwrap(),
+        lamports_before
+    );
+}
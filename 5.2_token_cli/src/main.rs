@@ -176,53 +176,94 @@
 //     Ok(())
 // }
 
-use borsh::{BorshDeserialize, BorshSerialize};
 use solana_client::rpc_client::RpcClient;
-use solana_program::{
-    instruction::{AccountMeta, Instruction},
-    pubkey::Pubkey,
-};
-use solana_sdk::signature::read_keypair_file;
+use solana_program::{pubkey, pubkey::Pubkey};
 use solana_sdk::{
-    signature::{Keypair, Signer},
-    sysvar,
+    signature::{Keypair, Signer, read_keypair_file},
     transaction::Transaction,
 };
-use spl_associated_token_account::get_associated_token_address;
-use std::{fmt::Debug, str::FromStr};
-
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub enum TokenInstruction {
-    CreateToken { decimals: u8 },
-    Mint { amount: u64 },
+use std::{env, error::Error, str::FromStr};
+
+// 指令构建函数由合约 crate 提供（启用 no-entrypoint 特性），账户顺序与 Processor 保持一致
+use token::instruction as token_instruction;
+
+// 默认连接本地验证节点
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+// 默认程序ID（可通过 TOKEN_CLI_PROGRAM_ID 覆盖）
+const DEFAULT_PROGRAM_ID: Pubkey = pubkey!("J1BeBUsTPQdbfxRTTSQEXjf1MAieGwKqAWvojbkQQKgg");
+// 未指定铸造数量时铸造 1000 个代币（6 位小数）
+const DEFAULT_MINT_AMOUNT: u64 = 1_000_000_000;
+
+/// 运行配置，从环境变量读取：
+/// - `TOKEN_CLI_RPC_URL`：RPC 地址，默认本地验证节点
+/// - `TOKEN_CLI_KEYPAIR`：支付账户密钥文件，默认 `~/.config/solana/id.json`
+/// - `TOKEN_CLI_PROGRAM_ID`：合约程序ID
+struct Config {
+    rpc_url: String,
+    keypair_path: String,
+    program_id: Pubkey,
 }
 
-fn main() {}
-
-#[test]
-fn test_fn() {
-    let rpc_client = RpcClient::new("http://127.0.0.1:8899".to_string());
-    let payer = read_keypair_file("/Users/milo/.config/solana/ad2.json").expect("failed");
+impl Config {
+    fn from_env() -> Result<Self, Box<dyn Error>> {
+        let rpc_url = env::var("TOKEN_CLI_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+        let keypair_path = match env::var("TOKEN_CLI_KEYPAIR") {
+            Ok(path) => path,
+            Err(_) => format!("{}/.config/solana/id.json", env::var("HOME")?),
+        };
+        let program_id = match env::var("TOKEN_CLI_PROGRAM_ID") {
+            Ok(id) => Pubkey::from_str(&id)?,
+            Err(_) => DEFAULT_PROGRAM_ID,
+        };
+        Ok(Self {
+            rpc_url,
+            keypair_path,
+            program_id,
+        })
+    }
+}
 
-    let program_id = Pubkey::from_str("J1BeBUsTPQdbfxRTTSQEXjf1MAieGwKqAWvojbkQQKgg").unwrap(); // 你的程序ID
+/// 用法：cli [铸造数量]
+fn main() -> Result<(), Box<dyn Error>> {
+    let amount = match env::args().nth(1) {
+        Some(amount) => amount.parse()?,
+        None => DEFAULT_MINT_AMOUNT,
+    };
+    run(&Config::from_env()?, amount)
+}
 
+/// 创建一个由 payer 担任铸币权限的代币，并给 payer 铸造 `amount`
+fn run(config: &Config, amount: u64) -> Result<(), Box<dyn Error>> {
+    let rpc_client = RpcClient::new(config.rpc_url.clone());
+    let payer = read_keypair_file(&config.keypair_path)?;
     let mint_account = Keypair::new();
-
-    println!("{:?}", mint_account.to_base58_string());
-    println!("{:?}", mint_account.pubkey().to_string());
+    println!("Mint: {}", mint_account.pubkey());
 
     // 1. 创建SPL Token
     create_token(
         &rpc_client,
-        &program_id,
+        &config.program_id,
         &payer,
         &mint_account,
         &payer.pubkey(),
         6,
-    );
+    )?;
 
     // 2. mint
-    mint(&rpc_client, &program_id, &mint_account, &payer, 1000000000);
+    mint(
+        &rpc_client,
+        &config.program_id,
+        &mint_account,
+        &payer,
+        amount,
+    )
+}
+
+// 需要本地验证节点，密钥文件等通过环境变量指定（见 Config）
+#[test]
+fn test_fn() {
+    let config = Config::from_env().expect("failed to read config");
+    run(&config, DEFAULT_MINT_AMOUNT).unwrap();
 }
 
 fn create_token(
@@ -233,37 +274,34 @@ fn create_token(
     mint_authority: &Pubkey,
     decimals: u8,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // 将指令序列化为字节数组
-    let instruction_data = borsh::to_vec(&TokenInstruction::CreateToken { decimals }).unwrap();
-
-    // 构建账户元数据
-    let accounts = vec![
-        AccountMeta::new(mint_account.pubkey(), true),
-        AccountMeta::new_readonly(*mint_authority, false),
-        AccountMeta::new_readonly(payer.pubkey(), false),
-        AccountMeta::new_readonly(sysvar::rent::id(), false),
-        AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
-        AccountMeta::new_readonly(spl_token::id(), false),
-    ];
-
-    // 构建指令
-    let token_instruction = Instruction {
-        program_id: *program_id,
-        accounts,
-        data: instruction_data,
-    };
+    // 构建指令（旧版 SPL Token，不设冻结权限，不限最大发行量，无转账手续费）
+    let instruction = token_instruction::create_token(
+        program_id,
+        &spl_token::id(),
+        &mint_account.pubkey(),
+        mint_authority,
+        &payer.pubkey(),
+        decimals,
+        None,
+        u64::MAX,
+        None,
+        "Test Token".to_string(),
+        "TEST".to_string(),
+        String::new(),
+        None,
+    );
 
     // Step 4: Send the transaction
     let latest_blockhash = rpc_client.get_latest_blockhash()?;
     let tx = Transaction::new_signed_with_payer(
-        &[token_instruction],
+        &[instruction],
         Some(&payer.pubkey()),
         &[payer, mint_account], // Sign with both payer and mint account keypairs
         latest_blockhash,
     );
 
-    let r = rpc_client.send_and_confirm_transaction(&tx);
-    println!("{:?}", r.unwrap());
+    let signature = rpc_client.send_and_confirm_transaction(&tx)?;
+    println!("{}", signature);
 
     println!("Token created successfully.");
     Ok(())
@@ -276,41 +314,30 @@ fn mint(
     payer: &Keypair,
     amount: u64,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // 创建ATA账号
-    let ata = get_associated_token_address(&payer.pubkey(), &mint_account.pubkey());
-
-    let instruction_data: Vec<u8> = borsh::to_vec(&TokenInstruction::Mint { amount }).unwrap();
-    println!("{}", ata.to_string());
-    let accounts = vec![
-        AccountMeta::new(mint_account.pubkey(), true), // Mint 需要是可写的
-        // AccountMeta::new_readonly(payer.pubkey(), true),  // Payer 需要是签名者
-        AccountMeta::new(ata, false), // ATA 可写，但不需要 signer
-        AccountMeta::new_readonly(sysvar::rent::id(), false), // Rent Sysvar
-        AccountMeta::new(payer.pubkey(), true), // Payer 是可写的并且需要签名
-        AccountMeta::new_readonly(solana_sdk::system_program::id(), false), // 系统程序
-        AccountMeta::new_readonly(spl_token::id(), false), // SPL Token 程序
-        AccountMeta::new_readonly(spl_associated_token_account::id(), false), // SPL Token 程序
-    ];
-
-    // 构建指令
-    let token_instruction = Instruction {
-        program_id: *program_id,
-        accounts,
-        data: instruction_data,
-    };
+    // 构建指令（接收者为 payer 自己，ATA 不存在时由合约创建；payer 同时作为铸币权限）
+    let instruction = token_instruction::mint(
+        program_id,
+        &spl_token::id(),
+        &mint_account.pubkey(),
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[],
+        amount,
+    );
 
     // 发送交易
     let latest_blockhash = rpc_client.get_latest_blockhash()?;
     let tx = Transaction::new_signed_with_payer(
-        &[token_instruction],
+        &[instruction],
         Some(&payer.pubkey()),
-        &[payer, mint_account], // 使用 Payer 和 Mint 的签名
+        &[payer],
         latest_blockhash,
     );
 
-    let r = rpc_client.send_and_confirm_transaction(&tx);
-    println!("{:?}", r.unwrap());
+    let signature = rpc_client.send_and_confirm_transaction(&tx)?;
+    println!("{}", signature);
 
-    println!("Token created successfully.");
+    println!("Token minted successfully.");
     Ok(())
 }